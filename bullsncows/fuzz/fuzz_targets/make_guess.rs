@@ -0,0 +1,43 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use bullsncows::algos::algos_utils;
+use bullsncows::game::{Game, GameState};
+use bullsncows::ruleset::Ruleset;
+
+// Arbitrary strings as guesses, one per line. The first line is the alphabet of a game with
+// repeats whose secret is the second line, the other lines are guessed in that game and in the
+// classic one. A guess must get feedback exactly when it is a possible secret, and that feedback
+// must be the score of the guess.
+
+fn check_guess(game: &mut Game, guess: &str) {
+    if game.is_over() {
+        assert_eq!(game.make_guess(guess), None);
+        return;
+    }
+    let valid: bool = game.get_ruleset().is_valid(guess);
+    let answer: String = String::from(game.get_answer());
+    match game.make_guess(guess) {
+        Some(feedback) => {
+            assert!(valid, "{:?} accepted", guess);
+            assert_eq!(Some(feedback), game.get_feedback_kind().get_feedback(guess, &answer));
+            assert_eq!(game.get_state() == GameState::Won, guess == answer);
+        }
+        None => assert!(!valid, "{:?} refused", guess),
+    }
+}
+
+fuzz_target!(|data: &str| {
+    let mut lines = data.lines();
+    let alphabet: Vec<char> = lines.next().unwrap_or("").chars().collect();
+    let secret: &str = lines.next().unwrap_or("");
+    let rules = Ruleset::new(secret.chars().count(), &alphabet, true);
+    let mut game = Game::new_with_ruleset_and_answer(&rules, secret);
+    let mut classic = Game::new_with_answer("1234", &algos_utils::DEFAULT_ALPHABET.chars().collect::<Vec<char>>(), false, None).unwrap();
+    for guess in lines {
+        if let Some(game) = game.as_mut() {
+            check_guess(game, guess);
+        }
+        check_guess(&mut classic, guess);
+    }
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use bullsncows::algos::algos_utils::get_bulls_and_cows;
+use bullsncows::algos::feedback::{get_marks, FEEDBACK_KINDS};
+
+// Arbitrary pairs of strings scored against each other: numbers of different lengths have no
+// score, the others score at most one bull or cow per symbol, symmetrically, and every kind of
+// feedback is one its keeper can give.
+
+fuzz_target!(|data: (&str, &str)| {
+    let (guess, secret) = data;
+    let length: usize = guess.chars().count();
+    let score = get_bulls_and_cows(guess, secret);
+    assert_eq!(score.is_some(), length == secret.chars().count());
+    assert_eq!(score, get_bulls_and_cows(secret, guess));
+    assert_eq!(get_marks(guess, secret).is_some(), score.is_some());
+    let (bulls, cows) = match score {
+        Some(score) => score,
+        None => return,
+    };
+    assert!(bulls + cows <= length);
+    assert_eq!(get_bulls_and_cows(guess, guess), Some((length, 0)));
+    for kind in FEEDBACK_KINDS {
+        if let Some(feedback) = kind.get_feedback(guess, secret) {
+            assert!(kind.is_possible(feedback, length), "{:?} {:?}", kind, feedback);
+            assert_eq!(feedback.0 == length, guess == secret);
+        }
+    }
+});
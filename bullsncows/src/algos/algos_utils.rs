@@ -1,39 +1,87 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use crate::algos::feedback::FeedbackKind;
+use crate::ruleset::Ruleset;
 
-pub fn get_bulls_and_cows(number0: String, number1: String) -> Option<(usize, usize)> {
+// the feedback table takes the square of it in bytes
+pub const MAX_TABLE_NUMBERS: usize = 10_000;
+
+// repeated symbols are scored like in Mastermind: every symbol of the guess
+// is matched with at most one symbol of the other number
+pub fn get_bulls_and_cows<S0: AsRef<str>, S1: AsRef<str>>(number0: S0, number1: S1) -> Option<(usize, usize)> {
+    let (number0, number1) = (number0.as_ref(), number1.as_ref());
+    if number0.is_ascii() && number1.is_ascii() {
+        return get_bulls_and_cows_of_symbols(number0.as_bytes(), number1.as_bytes());
+    }
+    let symbols0: Vec<char> = number0.chars().collect();
+    let symbols1: Vec<char> = number1.chars().collect();
+    return get_bulls_and_cows_of_symbols(&symbols0, &symbols1);
+}
+
+fn get_bulls_and_cows_of_symbols<T: PartialEq>(number0: &[T], number1: &[T]) -> Option<(usize, usize)> {
     if number0.len() != number1.len() {
         return None;
     }
-    let bulls: usize = number0.chars().zip(number1.chars()).filter(|(a, b)| {
+    let bulls: usize = number0.iter().zip(number1.iter()).filter(|(a, b)| {
         a == b
     }).count();
-    let cows: usize = number0.chars().filter(|&c| {
-        number1.contains(c)
-    }).count() - bulls;
+    let mut common: usize = 0;
+    for (i, c) in number0.iter().enumerate() {
+        if number0[..i].contains(c) {
+            continue;
+        }
+        let count0 = number0[i..].iter().filter(|&x| x == c).count();
+        let count1 = number1.iter().filter(|&x| x == c).count();
+        common += count0.min(count1);
+    }
+    let cows: usize = common - bulls;
     return Some((bulls, cows));
-} 
+}
+
+pub const DEFAULT_ALPHABET: &str = "0123456789";
 
 pub fn generate_default_init_values_for_numbers() -> Vec<String> {
+    return generate_init_values_for_numbers(4);
+}
+
+pub fn generate_init_values_for_numbers(ndigits: usize) -> Vec<String> {
+    let digits: Vec<char> = DEFAULT_ALPHABET.chars().collect();
+    return generate_numbers(&digits, ndigits, false);
+}
+
+// all numbers of length ndigits over the alphabet in lexicographic order (by alphabet order)
+pub fn generate_numbers(alphabet: &[char], ndigits: usize, repeats_allowed: bool) -> Vec<String> {
     let mut numbers : Vec<String> = Vec::new();
-    let tmp_numbers : Vec<String> = (0..10000).map(|u| -> String {format!("{:04}", u)}).collect::<Vec<String>>();
-    for number in  tmp_numbers {
-        let mut valid = true;
-        let mut digits = HashSet::new();
-        for digit in number.chars() {
-            if digits.contains(&digit) {
-                valid = false;
-                break;
-            } else {
-                digits.insert(digit);
-            }
-        }
-        if valid {
-            numbers.push(number);
-        }            
+    if ndigits == 0 || (!repeats_allowed && ndigits > alphabet.len()) {
+        return numbers;
     }
+    let mut prefix: String = String::new();
+    let mut used_digits: HashSet<char> = HashSet::new();
+    extend_numbers(&mut numbers, &mut prefix, &mut used_digits, alphabet, ndigits, repeats_allowed);
     return numbers;
 }
 
+fn extend_numbers(numbers: &mut Vec<String>, prefix: &mut String, used_digits: &mut HashSet<char>, alphabet: &[char], ndigits: usize, repeats_allowed: bool) {
+    if prefix.chars().count() == ndigits {
+        numbers.push(prefix.clone());
+        return;
+    }
+    for &digit in alphabet {
+        if !repeats_allowed && used_digits.contains(&digit) {
+            continue;
+        }
+        prefix.push(digit);
+        used_digits.insert(digit);
+        extend_numbers(numbers, prefix, used_digits, alphabet, ndigits, repeats_allowed);
+        used_digits.remove(&digit);
+        prefix.pop();
+    }
+}
+
+// the solvers are given secrets of the rules, some of them or all; only the length, which they rely on, is checked
+pub fn fit_rules(numbers: &[String], rules: &Ruleset) -> bool {
+    return !numbers.is_empty() && numbers.iter().all(|number| number.chars().count() == rules.length);
+}
+
 pub fn generate_possible_feedbacks(ndigits:usize) -> Vec<(usize, usize)> {
     let mut possible_feedbacks: Vec<(usize, usize)> = Vec::new();
     if ndigits == 1 {
@@ -51,6 +99,70 @@ pub fn generate_possible_feedbacks(ndigits:usize) -> Vec<(usize, usize)> {
     return possible_feedbacks;
 }
 
+// feedbacks are indexed by bulls * (ndigits + 1) + cows, cheaper than hashing them
+pub fn get_feedback_index(feedback: (usize, usize), ndigits: usize) -> usize {
+    return feedback.0 * (ndigits + 1) + feedback.1;
+}
+
+// how many of the numbers would give each feedback to the guess, indexed by `get_feedback_index`
+pub fn get_partition_sizes<S: AsRef<str>>(guess: &str, numbers: &[S], ndigits: usize) -> Vec<usize> {
+    return get_partition_sizes_of_kind(FeedbackKind::BullsAndCows, guess, numbers, ndigits);
+}
+
+// like `get_partition_sizes` with the feedback a keeper of the given kind tells, see `count_partition_sizes`
+pub fn get_partition_sizes_of_kind<S: AsRef<str>>(kind: FeedbackKind, guess: &str, numbers: &[S], ndigits: usize) -> Vec<usize> {
+    return count_partition_sizes(numbers.iter().map(|number| kind.get_index(kind.get_feedback(guess, number).unwrap(), ndigits)), ndigits);
+}
+
+// how many times each feedback index (see `FeedbackKind::get_index`) is met, the bulls and cows indices in place;
+// the positional patterns, too many to list for long guesses, follow in the order they are met
+pub fn count_partition_sizes<I: IntoIterator<Item = usize>>(indices: I, ndigits: usize) -> Vec<usize> {
+    let cells: usize = (ndigits + 1) * (ndigits + 1);
+    let mut sizes: Vec<usize> = vec![0; cells];
+    let mut slots: HashMap<usize, usize> = HashMap::new();
+    for index in indices {
+        let slot: usize = if index < cells { index } else { *slots.entry(index).or_insert(sizes.len()) };
+        if slot == sizes.len() {
+            sizes.push(0);
+        }
+        sizes[slot] += 1;
+    }
+    return sizes;
+}
+
+// feedback index of numbers[guess] against numbers[secret] at guess * numbers.len() + secret, see `FeedbackKind::get_index`;
+// None beyond `MAX_TABLE_NUMBERS` numbers, when a byte cannot hold every feedback index of the kind or the lengths differ
+pub fn build_feedback_table(numbers: &[String], kind: FeedbackKind) -> Option<Vec<u8>> {
+    let ndigits: usize = numbers.first()?.chars().count();
+    if numbers.len() > MAX_TABLE_NUMBERS || ndigits > get_max_table_length(kind) {
+        return None;
+    }
+    let mut feedbacks: Vec<u8> = Vec::with_capacity(numbers.len() * numbers.len());
+    for guess in numbers.iter() {
+        for secret in numbers.iter() {
+            let feedback: (usize, usize) = kind.get_feedback(guess, secret)?;
+            feedbacks.push(kind.get_index(feedback, ndigits) as u8);
+        }
+    }
+    return Some(feedbacks);
+}
+
+// the longest numbers whose feedback indices of the kind a byte of the feedback table holds
+pub fn get_max_table_length(kind: FeedbackKind) -> usize {
+    return (1..).take_while(|&ndigits| kind.get_index_count(ndigits) <= u8::MAX as usize + 1).last().unwrap_or(0);
+}
+
+// the numbers consistent with every feedback, a guess still waiting for its feedback (usize::MAX, usize::MAX)
+// only rules itself out
+pub fn filter_numbers(numbers: &[String], past_guesses: &[(String, (usize, usize))], kind: FeedbackKind) -> Vec<String> {
+    return numbers.iter().filter(|number| past_guesses.iter().all(|(guess, feedback)| {
+        if *feedback == (usize::MAX, usize::MAX) {
+            return *number != guess;
+        }
+        kind.get_feedback(guess, number) == Some(*feedback)
+    })).cloned().collect();
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -62,6 +174,16 @@ mod tests {
         assert_eq!(get_bulls_and_cows(String::from("1234"), String::from("1235")), Some((3, 0)));
         assert_eq!(get_bulls_and_cows(String::from("1234"), String::from("1256")), Some((2, 0)));
         assert_eq!(get_bulls_and_cows(String::from("1234"), String::from("1243")), Some((2, 2)));
+        assert_eq!(get_bulls_and_cows(String::from("1234"), String::from("123")), None);
+    }
+    #[test]
+    fn test_get_bulls_and_cows_with_repeats() {
+        use super::get_bulls_and_cows;
+        assert_eq!(get_bulls_and_cows(String::from("1123"), String::from("1000")), Some((1, 0)));
+        assert_eq!(get_bulls_and_cows(String::from("1100"), String::from("0011")), Some((0, 4)));
+        assert_eq!(get_bulls_and_cows(String::from("1122"), String::from("1212")), Some((2, 2)));
+        assert_eq!(get_bulls_and_cows(String::from("1111"), String::from("1234")), Some((1, 0)));
+        assert_eq!(get_bulls_and_cows(String::from("1234"), String::from("1111")), Some((1, 0)));
     }
     #[test]
     fn test_generate_default_init_values_for_numbers_output() {
@@ -78,6 +200,86 @@ mod tests {
         }
     }
     #[test]
+    fn test_generate_init_values_for_numbers() {
+        use super::{generate_default_init_values_for_numbers, generate_init_values_for_numbers};
+        assert_eq!(generate_init_values_for_numbers(4), generate_default_init_values_for_numbers());
+        assert_eq!(generate_init_values_for_numbers(1).len(), 10);
+        assert_eq!(generate_init_values_for_numbers(3).len(), 720);
+        assert_eq!(generate_init_values_for_numbers(0).len(), 0);
+        assert_eq!(generate_init_values_for_numbers(11).len(), 0);
+        let numbers = generate_init_values_for_numbers(2);
+        assert_eq!(numbers[0], String::from("01"));
+        assert_eq!(numbers[numbers.len()-1], String::from("98"));
+    }
+    #[test]
+    fn test_generate_numbers() {
+        use super::generate_numbers;
+        let alphabet: Vec<char> = "abc".chars().collect();
+        assert_eq!(generate_numbers(&alphabet, 2, false), vec!["ab", "ac", "ba", "bc", "ca", "cb"]);
+        assert_eq!(generate_numbers(&alphabet, 2, true).len(), 9);
+        assert_eq!(generate_numbers(&alphabet, 2, true)[0], String::from("aa"));
+        assert_eq!(generate_numbers(&alphabet, 4, false).len(), 0);
+        assert_eq!(generate_numbers(&alphabet, 4, true).len(), 81);
+    }
+    #[test]
+    fn test_get_partition_sizes() {
+        use super::{get_feedback_index, get_partition_sizes};
+        let numbers = vec!["1234", "1243", "5678", "4321"];
+        let sizes = get_partition_sizes("1234", &numbers, 4);
+        assert_eq!(sizes.len(), 25);
+        assert_eq!(sizes[get_feedback_index((4, 0), 4)], 1);
+        assert_eq!(sizes[get_feedback_index((2, 2), 4)], 1);
+        assert_eq!(sizes[get_feedback_index((0, 0), 4)], 1);
+        assert_eq!(sizes[get_feedback_index((0, 4), 4)], 1);
+        assert_eq!(sizes.iter().sum::<usize>(), 4);
+    }
+    #[test]
+    fn test_count_partition_sizes() {
+        use super::{count_partition_sizes, get_feedback_index, get_partition_sizes_of_kind};
+        use crate::algos::feedback::FeedbackKind;
+        assert_eq!(count_partition_sizes([0, 24, 0], 4).len(), 25);
+        // the patterns met are counted after the 25 bulls and cows indices, the win in place
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("1243"), String::from("5678")];
+        let sizes = get_partition_sizes_of_kind(FeedbackKind::Positional, "1234", &numbers, 4);
+        assert_eq!(sizes.len(), 25 + 2);
+        assert_eq!(sizes[get_feedback_index((4, 0), 4)], 1);
+        assert_eq!(sizes[25..].to_vec(), vec![2, 1]);
+        // no room for 3^20 patterns is needed
+        let guess: String = "abcdefghijklmnopqrst".to_string();
+        let sizes = get_partition_sizes_of_kind(FeedbackKind::Positional, &guess, std::slice::from_ref(&guess), 20);
+        assert_eq!(sizes.iter().sum::<usize>(), 1);
+    }
+    #[test]
+    fn test_filter_numbers() {
+        use super::filter_numbers;
+        use crate::algos::feedback::FeedbackKind;
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("5678"), String::from("4321")];
+        let past_guesses = vec![(String::from("1234"), (2, 2))];
+        assert_eq!(filter_numbers(&numbers, &past_guesses, FeedbackKind::BullsAndCows), vec![String::from("1243")]);
+        let past_guesses = vec![(String::from("1234"), (usize::MAX, usize::MAX))];
+        assert_eq!(filter_numbers(&numbers, &past_guesses, FeedbackKind::BullsAndCows), numbers[1..].to_vec());
+        let past_guesses = vec![(String::from("1234"), (0, 4))];
+        assert_eq!(filter_numbers(&numbers, &past_guesses, FeedbackKind::Total), vec![String::from("1243"), String::from("4321")]);
+    }
+    #[test]
+    fn test_build_feedback_table() {
+        use super::{build_feedback_table, get_feedback_index};
+        use crate::algos::feedback::FeedbackKind;
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("5678")];
+        let table = build_feedback_table(&numbers, FeedbackKind::BullsAndCows).unwrap();
+        assert_eq!(table.len(), 9);
+        assert_eq!(table[1] as usize, get_feedback_index((2, 2), 4));
+        assert_eq!(table[3 + 1] as usize, get_feedback_index((4, 0), 4));
+        assert_eq!(table[2 * 3] as usize, get_feedback_index((0, 0), 4));
+        // 3^5 patterns do not fit a byte
+        let numbers = vec![String::from("12345"), String::from("12354")];
+        assert!(build_feedback_table(&numbers, FeedbackKind::Positional).is_none());
+        assert!(build_feedback_table(&[String::from("1234"), String::from("123")], FeedbackKind::BullsAndCows).is_none());
+        assert!(build_feedback_table(&[], FeedbackKind::BullsAndCows).is_none());
+        assert_eq!(super::get_max_table_length(FeedbackKind::BullsAndCows), 15);
+        assert_eq!(super::get_max_table_length(FeedbackKind::Positional), 4);
+    }
+    #[test]
     fn test_generate_possible_feedbacks() {
         use super::generate_possible_feedbacks;
         let possible_feedbacks = generate_possible_feedbacks(1);
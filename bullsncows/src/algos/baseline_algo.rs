@@ -1,37 +1,53 @@
-use std::collections::HashSet;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use crate::algos::algos_utils;
+use rand::SeedableRng;
+use crate::algos::{algos_utils, Solver};
+use crate::algos::feedback::FeedbackKind;
+use crate::ruleset::Ruleset;
 pub struct BaselineAlgo {
     numbers : Vec<String>,
+    // the numbers before any feedback, to recompute them when feedback is undone or corrected
+    universe : Vec<String>,
     past_guesses : Vec<(String, (usize, usize))>,
     last_guess_updated : bool,
     ndigits : usize,
+    rng : StdRng,
+    feedback_kind : FeedbackKind,
 }
 
 impl BaselineAlgo {
-    pub fn new(numbers: Vec<String>) -> Option<BaselineAlgo> {
-        if numbers.len() == 0 {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset) -> Option<BaselineAlgo> {
+        return BaselineAlgo::new_with_rng(numbers, rules, StdRng::from_entropy());
+    }
+
+    // the same seed and numbers always give the same sequence of guesses
+    pub fn new_with_seed(numbers: Vec<String>, rules: &Ruleset, seed: u64) -> Option<BaselineAlgo> {
+        return BaselineAlgo::new_with_rng(numbers, rules, StdRng::seed_from_u64(seed));
+    }
+
+    fn new_with_rng(numbers: Vec<String>, rules: &Ruleset, rng: StdRng) -> Option<BaselineAlgo> {
+        if !algos_utils::fit_rules(&numbers, rules) {
             return None;
         }
 
         let past_guesses: Vec<(String, (usize, usize))> = Vec::new();
         let last_guess_updated: bool = true;
-        let ndigits: usize = numbers[0].len();
-        return Some(BaselineAlgo {numbers, past_guesses, last_guess_updated, ndigits}); 
+        let ndigits: usize = rules.length;
+        let universe: Vec<String> = numbers.clone();
+        return Some(BaselineAlgo {numbers, universe, past_guesses, last_guess_updated, ndigits, rng, feedback_kind: rules.feedback_kind}); 
     }
 
     pub fn guess(&mut self) -> Option<String> {
         // written explicitly to handle exceptions differently in the future
-        if self.last_guess_updated == false {
+        if !self.last_guess_updated {
             return None;
         }
         if self.numbers.is_empty() {
             return None;
         }
         
-        let mut best_guess: String = String::new(); 
-        
-        best_guess = self.numbers.choose(&mut rand::thread_rng()).unwrap().to_string();
+        let best_guess: String = self.numbers.choose(&mut self.rng).unwrap().to_string();
         self.numbers.retain(|x: &String| x != &best_guess);
         self.past_guesses.push((best_guess.clone(), (usize::MAX, usize::MAX)));
         self.last_guess_updated = false;
@@ -41,6 +57,10 @@ impl BaselineAlgo {
     pub fn get_numbers_count(&self) -> usize {
         return self.numbers.len();
     }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return &self.numbers;
+    }
     
     fn get_last_guess(&self) -> Option<(String, (usize, usize))> {
         if self.past_guesses.is_empty() {
@@ -51,10 +71,10 @@ impl BaselineAlgo {
     }
 
     pub fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
-        if bulls + cows > self.ndigits || (bulls == self.ndigits-1 && cows == 1) {
+        if !self.feedback_kind.is_possible((bulls, cows), self.ndigits) {
             return None;
         }
-        else if self.last_guess_updated == true {
+        else if self.last_guess_updated {
             return Some(false);
         }
         self.past_guesses.last_mut().unwrap().1 = (bulls, cows);
@@ -64,14 +84,37 @@ impl BaselineAlgo {
         return Some(true);    
     }
 
+    // forgets the last guess, answered or not, false when there is none
+    pub fn undo_last_guess(&mut self) -> bool {
+        if self.past_guesses.pop().is_none() {
+            return false;
+        }
+        self.last_guess_updated = true;
+        self.numbers = algos_utils::filter_numbers(&self.universe, &self.past_guesses, self.feedback_kind);
+        return true;
+    }
+
+    // corrects the feedback of an answered guess, counted from 0, and recomputes the numbers from the original ones
+    pub fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        if !self.feedback_kind.is_possible((bulls, cows), self.ndigits) {
+            return None;
+        }
+        match self.past_guesses.get_mut(index) {
+            Some((_, feedback)) if *feedback != (usize::MAX, usize::MAX) => *feedback = (bulls, cows),
+            _ => return Some(false),
+        }
+        self.numbers = algos_utils::filter_numbers(&self.universe, &self.past_guesses, self.feedback_kind);
+        return Some(true);
+    }
+
     fn find_valid_numbers(&self, guess: (String, (usize, usize))) -> Option<Vec<String>> {
-        if guess.0.len() != self.ndigits || guess.1.0 + guess.1.1 > self.ndigits {
+        if guess.0.chars().count() != self.ndigits || !self.feedback_kind.is_possible(guess.1, self.ndigits) {
             return None;
         }
 
         let mut valid_numbers:Vec<String> = Vec::new();
         for number in self.numbers.iter() {
-            let bnc: (usize, usize) = algos_utils::get_bulls_and_cows(guess.0.clone(), number.clone()).unwrap();
+            let bnc: (usize, usize) = self.feedback_kind.get_feedback(&guess.0, number).unwrap();
             if bnc.0 == guess.1.0 && bnc.1 == guess.1.1 {
                 valid_numbers.push(number.clone());
             }
@@ -81,15 +124,43 @@ impl BaselineAlgo {
     }
 }
 
+impl Solver for BaselineAlgo {
+    fn guess(&mut self) -> Option<String> {
+        return BaselineAlgo::guess(self);
+    }
+
+    fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return BaselineAlgo::incorporate_guess_feedback(self, bulls, cows);
+    }
+
+    fn undo_last_guess(&mut self) -> bool {
+        return BaselineAlgo::undo_last_guess(self);
+    }
+
+    fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return BaselineAlgo::revise_feedback(self, index, bulls, cows);
+    }
+
+    fn get_numbers_count(&self) -> usize {
+        return BaselineAlgo::get_numbers_count(self);
+    }
+
+    fn get_numbers(&self) -> &[String] {
+        return BaselineAlgo::get_numbers(self);
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::len_zero, clippy::useless_vec)]
 mod tests {
-    use crate::algos::algos_utils::generate_default_init_values_for_numbers;
+    use crate::algos::algos_utils::{generate_default_init_values_for_numbers, get_bulls_and_cows};
     use super::BaselineAlgo;
+    use crate::ruleset::Ruleset;
     
     #[test]
     fn test_baseline_algo_new() {
         let numbers = generate_default_init_values_for_numbers();
-        let ba = BaselineAlgo::new(numbers);
+        let ba = BaselineAlgo::new(numbers, &Ruleset::classic());
         assert_eq!(ba.as_ref().is_some(), true);
         assert!(ba.as_ref().unwrap().numbers.len() > 0);
         assert_eq!(ba.as_ref().unwrap().past_guesses.len(), 0);
@@ -101,16 +172,16 @@ mod tests {
     #[test]
     fn test_baseline_algo_get_numbers_count() {
         let numbers = generate_default_init_values_for_numbers();
-        let ba = BaselineAlgo::new(numbers).unwrap();
+        let ba = BaselineAlgo::new(numbers, &Ruleset::classic()).unwrap();
         assert_eq!(ba.get_numbers_count(), 5040);
         let numbers = vec![String::from("1234"), String::from("5678")];
-        let ba = BaselineAlgo::new(numbers).unwrap();
+        let ba = BaselineAlgo::new(numbers, &Ruleset::classic()).unwrap();
         assert_eq!(ba.get_numbers_count(), 2);
     }
     #[test]
     fn test_basic_baseline_algo_guess() {
         let numbers = generate_default_init_values_for_numbers();
-        let mut ba = super::BaselineAlgo::new(numbers).unwrap();
+        let mut ba = super::BaselineAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = ba.guess();
         assert_eq!(guess.as_ref().is_some(), true);
         assert_eq!(ba.numbers.len(), 5039);
@@ -123,7 +194,7 @@ mod tests {
         assert_eq!(guess.as_ref().is_some(), false);
 
         let numbers = vec![String::from("1234"), String::from("5678")];
-        let mut ba = super::BaselineAlgo::new(numbers).unwrap();
+        let mut ba = super::BaselineAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = ba.guess();
         assert_eq!(guess.as_ref().is_some(), true);
         assert_eq!(ba.numbers.len(), 1);
@@ -135,7 +206,7 @@ mod tests {
     #[test]
     fn test_basic_baseline_algo_incorporate_guess_feedback() {
         let numbers = generate_default_init_values_for_numbers();
-        let mut ba = BaselineAlgo::new(numbers).unwrap();
+        let mut ba = BaselineAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = ba.guess().unwrap();
         let res = ba.incorporate_guess_feedback(4, 0);
         assert_eq!(res.as_ref().is_some(), true);
@@ -147,11 +218,34 @@ mod tests {
         assert_eq!(ba.past_guesses[0].1, (4, 0));
         
         let numbers = generate_default_init_values_for_numbers();
-        let mut ba = BaselineAlgo::new(numbers).unwrap();
-        let guess = ba.guess().unwrap();
+        let mut ba = BaselineAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        ba.guess().unwrap();
         let res = ba.incorporate_guess_feedback(5, 0);
         assert_eq!(res.as_ref().is_some(), false);
     }
+    #[test]
+    fn test_baseline_algo_new_with_seed() {
+        let numbers = generate_default_init_values_for_numbers();
+        let mut ba0 = BaselineAlgo::new_with_seed(numbers.clone(), &Ruleset::classic(), 7).unwrap();
+        let mut ba1 = BaselineAlgo::new_with_seed(numbers, &Ruleset::classic(), 7).unwrap();
+        assert_eq!(ba0.guess(), ba1.guess());
+        ba0.incorporate_guess_feedback(0, 1).unwrap();
+        ba1.incorporate_guess_feedback(0, 1).unwrap();
+        assert_eq!(ba0.guess(), ba1.guess());
+    }
+    #[test]
+    fn test_baseline_algo_undo_and_revise_feedback() {
+        let numbers = generate_default_init_values_for_numbers();
+        let mut ba = BaselineAlgo::new_with_seed(numbers.clone(), &Ruleset::classic(), 3).unwrap();
+        let guess = ba.guess().unwrap();
+        assert_eq!(ba.revise_feedback(0, 1, 1), Some(false));
+        ba.incorporate_guess_feedback(0, 0).unwrap();
+        assert_eq!(ba.revise_feedback(0, 0, 4), Some(true));
+        assert!(ba.get_numbers().iter().all(|number| get_bulls_and_cows(&guess, number) == Some((0, 4))));
+        assert!(ba.undo_last_guess());
+        assert_eq!(ba.get_numbers(), numbers);
+        assert!(!ba.undo_last_guess());
+    }
 }
 
 // additional comments
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::thread;
+use crate::algos::{algos_utils, symmetry};
+use crate::algos::feedback::FeedbackKind;
+use crate::algos::heuristics::Heuristic;
+use crate::algos::symmetry::Symmetries;
+use crate::ruleset::Ruleset;
+
+// the guesses made in one game with the feedback they got
+pub type History = Vec<(String, (usize, usize))>;
+
+// Suggests the next guess for many games over the same numbers at once, e.g. for a server.
+// The numbers, their feedback table and the opening guess are computed once and shared by
+// every game instead of building a `DepthOneEntropyAlgo` per game; the suggestions are the
+// ones it would make. Games are spread over the available threads.
+pub struct BatchSolver {
+    numbers : Vec<String>,
+    indices : HashMap<String, u32>,
+    ndigits : usize,
+    heuristic : Heuristic,
+    feedback_kind : FeedbackKind,
+    // set when the numbers are every number over an alphabet, equivalent guesses are then scored once
+    universe_alphabet : Option<Vec<char>>,
+    // see `algos_utils::build_feedback_table`, None when it cannot be built and feedback is computed when needed
+    feedbacks : Option<Vec<u8>>,
+    // the suggestion for a game without guesses
+    opening : Option<(String, f64)>,
+}
+
+impl BatchSolver {
+    // for games over the numbers, secrets of the rules, whose keeper tells the feedback of the rules; the histories hold it
+    pub fn new(numbers: Vec<String>, rules: &Ruleset, heuristic: Heuristic) -> Option<BatchSolver> {
+        if !algos_utils::fit_rules(&numbers, rules) {
+            return None;
+        }
+        let ndigits: usize = rules.length;
+        let feedback_kind: FeedbackKind = rules.feedback_kind;
+        let indices: HashMap<String, u32> = numbers.iter().enumerate().map(|(i, number)| (number.clone(), i as u32)).collect();
+        let universe_alphabet: Option<Vec<char>> = symmetry::get_universe_alphabet(&numbers, rules);
+        let feedbacks: Option<Vec<u8>> = algos_utils::build_feedback_table(&numbers, feedback_kind);
+        let mut solver = BatchSolver {numbers, indices, ndigits, heuristic, feedback_kind, universe_alphabet, feedbacks, opening: None};
+        solver.opening = solver.calculate_suggestion(&[]);
+        return Some(solver);
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return &self.numbers;
+    }
+
+    // the next guess with its score for every game, in order,
+    // None for a game without consistent numbers or with a guess or feedback that does not fit the numbers
+    pub fn suggest_guesses(&self, histories: &[History]) -> Vec<Option<(String, f64)>> {
+        let threads: usize = thread::available_parallelism().map_or(1, |threads| threads.get());
+        let chunk_size: usize = histories.len().div_ceil(threads).max(1);
+        return thread::scope(|scope| {
+            let handles: Vec<_> = histories.chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(|history| self.suggest_guess(history)).collect::<Vec<_>>()))
+                .collect();
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        });
+    }
+
+    // the next guess with its score for one game
+    pub fn suggest_guess(&self, history: &[(String, (usize, usize))]) -> Option<(String, f64)> {
+        if history.is_empty() {
+            return self.opening.clone();
+        }
+        return self.calculate_suggestion(history);
+    }
+
+    fn calculate_suggestion(&self, history: &[(String, (usize, usize))]) -> Option<(String, f64)> {
+        let candidates: Vec<u32> = self.find_candidates(history)?;
+        let symmetries: Symmetries = match &self.universe_alphabet {
+            Some(alphabet) => Symmetries::new_for_kind(alphabet, self.ndigits, &history.iter().map(|(guess, _)| guess.as_str()).collect::<Vec<&str>>(), self.feedback_kind),
+            None => Symmetries::none(),
+        };
+        let candidate_numbers: Vec<&str> = candidates.iter().map(|&number| self.numbers[number as usize].as_str()).collect();
+
+        let mut best: Option<(String, f64)> = None;
+        for &guess in symmetries.get_representatives(&candidate_numbers) {
+            let guess_index: u32 = self.indices[guess];
+            let partition_sizes: Vec<usize> = algos_utils::count_partition_sizes(candidates.iter().map(|&secret| self.get_feedback_index(guess_index, secret)), self.ndigits);
+            // a single remaining number carries no information but is the answer
+            let score: f64 = self.heuristic.score(&partition_sizes, self.ndigits).unwrap_or(0.0);
+            if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+                best = Some((String::from(guess), score));
+            }
+        }
+        return best;
+    }
+
+    // indices of the numbers consistent with the history, guesses outside the numbers are scored directly
+    fn find_candidates(&self, history: &[(String, (usize, usize))]) -> Option<Vec<u32>> {
+        let mut candidates: Vec<u32> = (0..self.numbers.len() as u32).collect();
+        for (guess, feedback) in history.iter() {
+            if guess.chars().count() != self.ndigits || !self.feedback_kind.is_possible(*feedback, self.ndigits) {
+                return None;
+            }
+            let feedback_index: usize = self.feedback_kind.get_index(*feedback, self.ndigits);
+            match self.indices.get(guess) {
+                Some(&guess_index) => candidates.retain(|&secret| secret != guess_index && self.get_feedback_index(guess_index, secret) == feedback_index),
+                None => candidates.retain(|&secret| self.feedback_kind.get_feedback(guess, &self.numbers[secret as usize]) == Some(*feedback)),
+            }
+        }
+        return Some(candidates);
+    }
+
+    fn get_feedback_index(&self, guess: u32, secret: u32) -> usize {
+        return match &self.feedbacks {
+            Some(feedbacks) => feedbacks[guess as usize * self.numbers.len() + secret as usize] as usize,
+            None => {
+                let bnc = self.feedback_kind.get_feedback(&self.numbers[guess as usize], &self.numbers[secret as usize]).unwrap();
+                self.feedback_kind.get_index(bnc, self.ndigits)
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::algos_utils::{generate_default_init_values_for_numbers, get_bulls_and_cows};
+    use crate::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+    use crate::algos::heuristics::Heuristic;
+    use crate::ruleset::Ruleset;
+    use super::{BatchSolver, History};
+
+    #[test]
+    fn test_batch_solver_matches_depth_one_entropy_algo() {
+        let numbers = generate_default_init_values_for_numbers();
+        let histories: Vec<History> = vec![
+            Vec::new(),
+            vec![(String::from("0123"), (0, 2))],
+            vec![(String::from("0123"), (0, 2)), (String::from("1456"), (1, 1))],
+            vec![(String::from("9876"), (1, 0)), (String::from("0125"), (0, 1)), (String::from("3409"), (2, 0))],
+            // no number is consistent
+            vec![(String::from("0123"), (0, 0)), (String::from("4567"), (0, 0)), (String::from("8901"), (0, 0))],
+        ];
+        for heuristic in [Heuristic::Entropy, Heuristic::WorstCase] {
+            let batch = BatchSolver::new(numbers.clone(), &Ruleset::classic(), heuristic).unwrap();
+            let suggestions = batch.suggest_guesses(&histories);
+            assert_eq!(suggestions.len(), histories.len());
+            for (history, suggestion) in histories.iter().zip(suggestions) {
+                let mut doea = DepthOneEntropyAlgo::new_with_heuristic(numbers.clone(), &Ruleset::classic(), heuristic).unwrap();
+                for (guess, (bulls, cows)) in history.iter() {
+                    doea.incorporate_external_guess_feedback(guess.clone(), *bulls, *cows).unwrap();
+                }
+                assert_eq!(suggestion, doea.suggest_guess(), "{:?}", history);
+            }
+        }
+    }
+    #[test]
+    fn test_batch_solver_guesses_outside_the_numbers() {
+        let rules = Ruleset::new(3, &"abcdef".chars().collect::<Vec<char>>(), false);
+        let batch = BatchSolver::new(rules.generate_numbers(), &rules, Heuristic::Entropy).unwrap();
+        // "aab" cannot be the secret but still tells the numbers apart
+        let history: History = vec![(String::from("aab"), (1, 1))];
+        let (guess, _) = batch.suggest_guess(&history).unwrap();
+        assert_eq!(get_bulls_and_cows("aab", &guess), Some((1, 1)));
+        assert_eq!(batch.suggest_guess(&[(String::from("abcd"), (0, 0))]), None);
+        assert_eq!(batch.suggest_guesses(&[]), Vec::new());
+    }
+}
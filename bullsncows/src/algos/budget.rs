@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+// How much work a solver may do to choose a guess
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    // e.g. simulated games or scored guesses, depending on the solver
+    Iterations(usize),
+    Time(Duration),
+}
+
+// counts the work done against a budget, started when created
+pub struct BudgetTracker {
+    budget : Budget,
+    start : Instant,
+    iterations : usize,
+}
+
+impl BudgetTracker {
+    pub fn new(budget: Budget) -> BudgetTracker {
+        return BudgetTracker {budget, start: Instant::now(), iterations: 0};
+    }
+
+    pub fn record_iteration(&mut self) {
+        self.iterations += 1;
+    }
+
+    pub fn get_iterations(&self) -> usize {
+        return self.iterations;
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        match self.budget {
+            Budget::Iterations(iterations) => return self.iterations >= iterations,
+            Budget::Time(duration) => return self.start.elapsed() >= duration,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::{Budget, BudgetTracker};
+
+    #[test]
+    fn test_budget_tracker() {
+        let mut tracker = BudgetTracker::new(Budget::Iterations(2));
+        assert!(!tracker.is_exhausted());
+        tracker.record_iteration();
+        tracker.record_iteration();
+        assert!(tracker.is_exhausted());
+        assert_eq!(tracker.get_iterations(), 2);
+
+        assert!(!BudgetTracker::new(Budget::Time(Duration::from_secs(60))).is_exhausted());
+        assert!(BudgetTracker::new(Budget::Time(Duration::ZERO)).is_exhausted());
+    }
+}
@@ -1,58 +1,151 @@
-use std::collections::HashMap;
-
 // use std::collections::HashSet;
-use crate::algos::algos_utils;
+use crate::algos::{algos_utils, Solver};
+use crate::algos::budget::{Budget, BudgetTracker};
+use crate::algos::feedback::FeedbackKind;
+use crate::algos::heuristics::{self, Heuristic};
+use crate::algos::symmetry::{self, Symmetries};
+use crate::ruleset::Ruleset;
 // use crate::algos::algos_utils::{get_bulls_and_cows, generate_possible_feedbacks};
 
 pub struct DepthOneEntropyAlgo {
     numbers : Vec<String>,
+    // the numbers before any feedback, to recompute them when feedback is undone or corrected
+    universe : Vec<String>,
+    #[allow(dead_code)]
     possible_feedbacks : Vec<(usize, usize)>,
     past_guesses : Vec<(String, (usize, usize))>,
     last_guess_updated : bool,
     ndigits : usize,
+    heuristic : Heuristic,
+    // set when the numbers are every number over an alphabet, equivalent guesses are then scored once
+    universe_alphabet : Option<Vec<char>>,
+    // unlimited by default
+    budget : Option<Budget>,
+    // what the feedback tells, bulls and cows by default
+    feedback_kind : FeedbackKind,
 }
 
 impl DepthOneEntropyAlgo {
-    pub fn new(numbers: Vec<String>) -> Option<DepthOneEntropyAlgo> {
-        if numbers.len() == 0 {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset) -> Option<DepthOneEntropyAlgo> {
+        return DepthOneEntropyAlgo::new_with_heuristic(numbers, rules, Heuristic::Entropy);
+    }
+
+    // rates the guesses with another heuristic than the entropy
+    pub fn new_with_heuristic(numbers: Vec<String>, rules: &Ruleset, heuristic: Heuristic) -> Option<DepthOneEntropyAlgo> {
+        if !algos_utils::fit_rules(&numbers, rules) {
             return None;
         }
         let past_guesses: Vec<(String, (usize, usize))> = Vec::new();
         let last_guess_updated: bool = true;
-        let ndigits: usize = numbers[0].len();
+        let ndigits: usize = rules.length;
         let possible_feedbacks: Vec<(usize, usize)> = algos_utils::generate_possible_feedbacks(ndigits);
-        return Some(DepthOneEntropyAlgo {numbers, possible_feedbacks, past_guesses, last_guess_updated, ndigits}); 
+        let universe_alphabet: Option<Vec<char>> = symmetry::get_universe_alphabet(&numbers, rules);
+        let universe: Vec<String> = numbers.clone();
+        return Some(DepthOneEntropyAlgo {numbers, universe, possible_feedbacks, past_guesses, last_guess_updated, ndigits, heuristic, universe_alphabet, budget: None, feedback_kind: rules.feedback_kind});
     }
 
     pub fn guess(&mut self) -> Option<String> {
         // written explicitly to handle exceptions differently in the future
-        if self.last_guess_updated == false {
+        if !self.last_guess_updated {
             return None;
         }
-        if self.numbers.is_empty() {
+        let (best_guess, _) = self.suggest_guess()?;
+        return self.make_guess(best_guess);
+    }
+
+    // commits to a guess chosen elsewhere, e.g. by a deeper search, the feedback follows with `incorporate_guess_feedback`
+    pub fn make_guess(&mut self, guess: String) -> Option<String> {
+        if !self.last_guess_updated || guess.chars().count() != self.ndigits {
             return None;
         }
-        
-        let mut best_guess: String = String::new();
-        let mut best_entropy: f64 = f64::MIN;
-        for number in self.numbers.iter() {
-            let entropy: f64 = self.calculate_guess_entropy(number.clone()).unwrap();
-            if entropy > best_entropy {
-                best_entropy = entropy.clone();
-                best_guess = number.clone();
+        self.numbers.retain(|x: &String| x != &guess);
+        self.past_guesses.push((guess.clone(), (usize::MAX, usize::MAX)));
+        self.last_guess_updated = false;
+
+        return Some(guess);
+    }
+
+    pub fn is_waiting_for_feedback(&self) -> bool {
+        return !self.last_guess_updated;
+    }
+
+    // best guess with its score (the entropy in bits by default), without committing to it;
+    // with a budget, the best of the guesses scored before it ran out
+    pub fn suggest_guess(&self) -> Option<(String, f64)> {
+        let mut best: Option<(String, f64)> = None;
+        for (guess, score) in self.score_guesses(self.budget.map(BudgetTracker::new).as_mut()) {
+            if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+                best = Some((guess, score));
             }
         }
+        return best;
+    }
 
-        self.numbers.retain(|x: &String| x != &best_guess);
-        self.past_guesses.push((best_guess.clone(), (usize::MAX, usize::MAX)));
-        self.last_guess_updated = false;
+    // one remaining number per class of equivalent guesses with its score, best first, numbers with equal scores keep their order
+    pub fn rank_guesses(&self) -> Vec<(String, f64)> {
+        return self.rank_guesses_within(self.budget.map(BudgetTracker::new).as_mut());
+    }
+
+    // like `rank_guesses`, only the guesses scored before the budget ran out
+    pub fn rank_guesses_within(&self, budget: Option<&mut BudgetTracker>) -> Vec<(String, f64)> {
+        let mut ranked: Vec<(String, f64)> = self.score_guesses(budget);
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        return ranked;
+    }
+
+    pub fn set_budget(&mut self, budget: Option<Budget>) {
+        self.budget = budget;
+    }
+
+    pub fn get_feedback_kind(&self) -> FeedbackKind {
+        return self.feedback_kind;
+    }
+
+    // in the order of the numbers, or the most promising first when the budget may run out before all are scored,
+    // at least one guess is scored
+    fn score_guesses(&self, mut budget: Option<&mut BudgetTracker>) -> Vec<(String, f64)> {
+        let mut guesses: Vec<&String> = self.get_guess_representatives();
+        if budget.is_some() {
+            guesses = heuristics::order_promising_first(guesses, &self.numbers);
+        }
+        let mut scored: Vec<(String, f64)> = Vec::new();
+        for number in guesses {
+            // a single remaining number carries no information but is the answer
+            scored.push((number.clone(), self.calculate_guess_score(number).unwrap_or(0.0)));
+            if let Some(budget) = budget.as_deref_mut() {
+                budget.record_iteration();
+                if budget.is_exhausted() {
+                    break;
+                }
+            }
+        }
+        return scored;
+    }
+
+    // the first of the remaining numbers in every class of guesses that split them the same way
+    fn get_guess_representatives(&self) -> Vec<&String> {
+        return self.get_symmetries(&[]).get_representatives(&self.numbers);
+    }
 
-        return Some(best_guess);
+    // symmetries of the game so far, and after `next_guesses` if any
+    pub fn get_symmetries(&self, next_guesses: &[&str]) -> Symmetries {
+        let alphabet = match &self.universe_alphabet {
+            Some(alphabet) if !self.numbers.is_empty() => alphabet,
+            _ => return Symmetries::none(),
+        };
+        let mut past_guesses: Vec<&str> = self.past_guesses.iter().map(|(guess, _)| guess.as_str()).collect();
+        past_guesses.extend(next_guesses);
+        return Symmetries::new_for_kind(alphabet, self.ndigits, &past_guesses, self.feedback_kind);
     }
 
     pub fn get_numbers_count(&self) -> usize {
         return self.numbers.len();
     }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return &self.numbers;
+    }
     
     fn get_last_guess(&self) -> Option<(String, (usize, usize))> {
         if self.past_guesses.is_empty() {
@@ -63,10 +156,10 @@ impl DepthOneEntropyAlgo {
     }
 
     pub fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
-        if bulls + cows > self.ndigits || (bulls == self.ndigits-1 && cows == 1) {
+        if !self.feedback_kind.is_possible((bulls, cows), self.ndigits) {
             return None;
         }
-        else if self.last_guess_updated == true {
+        else if self.last_guess_updated {
             return Some(false);
         }
         self.past_guesses.last_mut().unwrap().1 = (bulls, cows);
@@ -76,14 +169,52 @@ impl DepthOneEntropyAlgo {
         return Some(true);    
     }
 
+    // forgets the last guess, answered or not, false when there is none
+    pub fn undo_last_guess(&mut self) -> bool {
+        if self.past_guesses.pop().is_none() {
+            return false;
+        }
+        self.last_guess_updated = true;
+        self.numbers = algos_utils::filter_numbers(&self.universe, &self.past_guesses, self.feedback_kind);
+        return true;
+    }
+
+    // corrects the feedback of an answered guess, counted from 0, and recomputes the numbers from the original ones
+    pub fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        if !self.feedback_kind.is_possible((bulls, cows), self.ndigits) {
+            return None;
+        }
+        match self.past_guesses.get_mut(index) {
+            Some((_, feedback)) if *feedback != (usize::MAX, usize::MAX) => *feedback = (bulls, cows),
+            _ => return Some(false),
+        }
+        self.numbers = algos_utils::filter_numbers(&self.universe, &self.past_guesses, self.feedback_kind);
+        return Some(true);
+    }
+
+    // feedback for a guess the solver did not propose, e.g. one typed in by a human
+    pub fn incorporate_external_guess_feedback(&mut self, guess: String, bulls: usize, cows: usize) -> Option<bool> {
+        if guess.chars().count() != self.ndigits || !self.feedback_kind.is_possible((bulls, cows), self.ndigits) {
+            return None;
+        }
+        else if !self.last_guess_updated {
+            return Some(false);
+        }
+        self.numbers.retain(|x: &String| x != &guess);
+        self.past_guesses.push((guess, (bulls, cows)));
+        self.numbers = self.find_valid_numbers(self.get_last_guess().unwrap()).unwrap();
+
+        return Some(true);
+    }
+
     fn find_valid_numbers(&self, guess: (String, (usize, usize))) -> Option<Vec<String>> {
-        if guess.0.len() != self.ndigits || guess.1.0 + guess.1.1 > self.ndigits {
+        if guess.0.chars().count() != self.ndigits || !self.feedback_kind.is_possible(guess.1, self.ndigits) {
             return None;
         }
 
         let mut valid_numbers:Vec<String> = Vec::new();
         for number in self.numbers.iter() {
-            let bnc: (usize, usize) = algos_utils::get_bulls_and_cows(guess.0.clone(), number.clone()).unwrap();
+            let bnc: (usize, usize) = self.feedback_kind.get_feedback(&guess.0, number).unwrap();
             if bnc.0 == guess.1.0 && bnc.1 == guess.1.1 {
                 valid_numbers.push(number.clone());
             }
@@ -92,37 +223,50 @@ impl DepthOneEntropyAlgo {
         return Some(valid_numbers);
     }
 
-    fn calculate_guess_entropy(&self, guess: String) -> Option<f64> {
-        if self.get_numbers_count() == 1 {
-            return None;
-        }
-        let numbers_but_guess_count: f64 = (self.get_numbers_count() - 1) as f64;
-        let mut entropy: f64 = 0.0;
-        let mut valid_numbers_count_per_feedback: HashMap<(usize, usize), usize> = HashMap::new();
-        for number in self.numbers.iter() {
-            let bnc:(usize, usize) = algos_utils::get_bulls_and_cows(number.clone(), guess.clone()).unwrap();
-            valid_numbers_count_per_feedback.entry(bnc).and_modify(|e| *e += 1).or_insert(1);
-        }
-        valid_numbers_count_per_feedback.remove_entry(&(self.ndigits.clone(),0));
+    fn calculate_guess_score(&self, guess: &str) -> Option<f64> {
+        let partition_sizes: Vec<usize> = algos_utils::get_partition_sizes_of_kind(self.feedback_kind, guess, &self.numbers, self.ndigits);
+        return self.heuristic.score(&partition_sizes, self.ndigits);
+    }
 
-        for count in valid_numbers_count_per_feedback.values() {
-            let probability: f64 = *count as f64 / numbers_but_guess_count;
-            entropy += -probability * probability.log2();
-        }
-        
-        return Some(entropy);
+}
+
+impl Solver for DepthOneEntropyAlgo {
+    fn guess(&mut self) -> Option<String> {
+        return DepthOneEntropyAlgo::guess(self);
     }
 
+    fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return DepthOneEntropyAlgo::incorporate_guess_feedback(self, bulls, cows);
+    }
+
+    fn undo_last_guess(&mut self) -> bool {
+        return DepthOneEntropyAlgo::undo_last_guess(self);
+    }
+
+    fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return DepthOneEntropyAlgo::revise_feedback(self, index, bulls, cows);
+    }
+
+    fn get_numbers_count(&self) -> usize {
+        return DepthOneEntropyAlgo::get_numbers_count(self);
+    }
+
+    fn get_numbers(&self) -> &[String] {
+        return DepthOneEntropyAlgo::get_numbers(self);
+    }
 }
 
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::len_zero, clippy::useless_vec)]
 mod tests {
     use crate::algos::algos_utils::generate_default_init_values_for_numbers;
     use super::DepthOneEntropyAlgo;
+    use crate::ruleset::Ruleset;
     
     #[test]
-    fn test_DepthOneEntropyAlgo_new() {
+    fn test_depth_one_entropy_algo_new() {
         let numbers = generate_default_init_values_for_numbers();
-        let doea = DepthOneEntropyAlgo::new(numbers);
+        let doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic());
         assert_eq!(doea.as_ref().is_some(), true);
         assert!(doea.as_ref().unwrap().numbers.len() > 0);
         assert!(doea.as_ref().unwrap().possible_feedbacks.len() > 0);
@@ -131,20 +275,24 @@ mod tests {
         assert_eq!(doea.as_ref().unwrap().ndigits, 4);
         let numbers = generate_default_init_values_for_numbers();
         assert_eq!(doea.as_ref().unwrap().numbers, numbers);
+        // the length is the one of the rules, not of the first number
+        assert!(DepthOneEntropyAlgo::new(vec![String::from("123")], &Ruleset::classic()).is_none());
+        assert!(DepthOneEntropyAlgo::new(vec![String::from("1234"), String::from("123")], &Ruleset::classic()).is_none());
+        assert!(DepthOneEntropyAlgo::new(Vec::new(), &Ruleset::classic()).is_none());
     }
     #[test]
     fn test_depth_one_entropy_algo_get_numbers_count() {
         let numbers = generate_default_init_values_for_numbers();
-        let doea = DepthOneEntropyAlgo::new(numbers).unwrap();
+        let doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         assert_eq!(doea.get_numbers_count(), 5040);
         let numbers = vec![String::from("1234"), String::from("5678")];
-        let doea = DepthOneEntropyAlgo::new(numbers).unwrap();
+        let doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         assert_eq!(doea.get_numbers_count(), 2);
     }
     #[test]
     fn test_basic_depth_one_entropy_algo_guess0() {
         let numbers = generate_default_init_values_for_numbers();
-        let mut doea = super::DepthOneEntropyAlgo::new(numbers).unwrap();
+        let mut doea = super::DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = doea.guess();
         assert_eq!(guess.as_ref().is_some(), true);
         assert_eq!(doea.numbers.len(), 5039);
@@ -159,7 +307,7 @@ mod tests {
     #[test]
     fn test_basic_depth_one_entropy_algo_guess1() {
         let numbers = vec![String::from("1234"), String::from("5678")];
-        let mut doea = super::DepthOneEntropyAlgo::new(numbers).unwrap();
+        let mut doea = super::DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = doea.guess();
         assert_eq!(guess.as_ref().is_some(), true);
         assert_eq!(doea.numbers.len(), 1);
@@ -169,7 +317,7 @@ mod tests {
         assert_eq!(doea.past_guesses[0].1, (usize::MAX, usize::MAX));
         
         let numbers = vec![String::from("1234"), String::from("1256"), String::from("7325"), String::from("2091"), String::from("9012"), String::from("1324"), String::from("7891")];
-        let mut doea = super::DepthOneEntropyAlgo::new(numbers).unwrap();
+        let mut doea = super::DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = doea.guess();
         assert_eq!(vec![String::from("1234"), String::from("1256"), String::from("1324")].contains(guess.as_ref().unwrap()), true);
         assert_eq!(guess.as_ref().unwrap(), &String::from("1324"));
@@ -177,15 +325,21 @@ mod tests {
     #[test]
     fn test_basic_depth_one_entropy_algo_guess2() {
         let numbers = vec![String::from("1234"), String::from("1256"), String::from("7325"), String::from("2091"), String::from("9012"), String::from("1324"), String::from("7891")];
-        let mut doea = super::DepthOneEntropyAlgo::new(numbers).unwrap();
+        let mut doea = super::DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = doea.guess();
         assert_eq!(vec![String::from("1234"), String::from("1256"), String::from("1324")].contains(guess.as_ref().unwrap()), true);
         assert_eq!(guess.as_ref().unwrap(), &String::from("1324"));
     }
     #[test]
+    fn test_basic_depth_one_entropy_algo_guess_last_number() {
+        let numbers = vec![String::from("1234")];
+        let mut doea = super::DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        assert_eq!(doea.guess(), Some(String::from("1234")));
+    }
+    #[test]
     fn test_basic_depth_one_entropy_algo_incorporate_guess_feedback() {
         let numbers = generate_default_init_values_for_numbers();
-        let mut doea = DepthOneEntropyAlgo::new(numbers).unwrap();
+        let mut doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
         let guess = doea.guess().unwrap();
         let res = doea.incorporate_guess_feedback(4, 0);
         assert_eq!(res.as_ref().is_some(), true);
@@ -197,9 +351,116 @@ mod tests {
         assert_eq!(doea.past_guesses[0].1, (4, 0));
         
         let numbers = generate_default_init_values_for_numbers();
-        let mut doea = DepthOneEntropyAlgo::new(numbers).unwrap();
-        let guess = doea.guess().unwrap();
+        let mut doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        doea.guess().unwrap();
         let res = doea.incorporate_guess_feedback(5, 0);
         assert_eq!(res.as_ref().is_some(), false);
     }
+    #[test]
+    fn test_depth_one_entropy_algo_suggest_guess() {
+        let numbers = vec![String::from("1234"), String::from("1256"), String::from("7325"), String::from("2091"), String::from("9012"), String::from("1324"), String::from("7891")];
+        let doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        let (guess, entropy) = doea.suggest_guess().unwrap();
+        assert_eq!(guess, String::from("1324"));
+        assert!(entropy > 0.0 && entropy <= (6.0f64).log2());
+        assert_eq!(doea.get_numbers_count(), 7);
+        assert_eq!(doea.past_guesses.len(), 0);
+
+        let doea = DepthOneEntropyAlgo::new(vec![String::from("1234")], &Ruleset::classic()).unwrap();
+        assert_eq!(doea.suggest_guess(), Some((String::from("1234"), 0.0)));
+    }
+    #[test]
+    fn test_depth_one_entropy_algo_other_heuristics() {
+        use crate::algos::heuristics::Heuristic;
+        // 1243 (and 2134) tell all the other numbers apart
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("1324"), String::from("2134"), String::from("5678")];
+        let doea = DepthOneEntropyAlgo::new_with_heuristic(numbers.clone(), &Ruleset::classic(), Heuristic::WorstCase).unwrap();
+        assert_eq!(doea.suggest_guess(), Some((String::from("1243"), -1.0)));
+        let doea = DepthOneEntropyAlgo::new_with_heuristic(numbers.clone(), &Ruleset::classic(), Heuristic::ExpectedSize).unwrap();
+        assert_eq!(doea.suggest_guess(), Some((String::from("1243"), -0.8)));
+        let doea = DepthOneEntropyAlgo::new_with_heuristic(numbers, &Ruleset::classic(), Heuristic::MostParts).unwrap();
+        assert_eq!(doea.suggest_guess(), Some((String::from("1243"), 5.0)));
+    }
+    #[test]
+    fn test_depth_one_entropy_algo_symmetric_guesses_scored_once() {
+        use crate::algos::algos_utils::get_partition_sizes;
+        use crate::algos::heuristics::Heuristic;
+        let numbers = generate_default_init_values_for_numbers();
+        let mut doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        assert_eq!(doea.rank_guesses().len(), 1);
+        doea.incorporate_external_guess_feedback(String::from("0123"), 0, 2).unwrap();
+        assert!(doea.rank_guesses().len() < doea.get_numbers_count() / 10);
+        // the same guess as when scoring every number
+        let mut best: (String, f64) = (String::new(), f64::MIN);
+        for number in doea.get_numbers().iter() {
+            let entropy = Heuristic::Entropy.score(&get_partition_sizes(number, doea.get_numbers(), 4), 4).unwrap();
+            if entropy > best.1 {
+                best = (number.clone(), entropy);
+            }
+        }
+        assert_eq!(doea.suggest_guess(), Some(best));
+    }
+    #[test]
+    fn test_depth_one_entropy_algo_with_budget() {
+        use std::time::Duration;
+        use crate::algos::budget::Budget;
+        let numbers = generate_default_init_values_for_numbers();
+        let mut doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        doea.incorporate_external_guess_feedback(String::from("0123"), 0, 2).unwrap();
+        doea.incorporate_external_guess_feedback(String::from("1456"), 1, 1).unwrap();
+        assert!(doea.rank_guesses().len() > 5);
+        let unlimited = doea.suggest_guess().unwrap();
+        doea.set_budget(Some(Budget::Iterations(usize::MAX)));
+        assert_eq!(doea.suggest_guess(), Some(unlimited.clone()));
+        doea.set_budget(Some(Budget::Iterations(5)));
+        assert_eq!(doea.rank_guesses().len(), 5);
+        // the most promising guesses are already good
+        let (_, entropy) = doea.suggest_guess().unwrap();
+        assert!(entropy > unlimited.1 * 0.9, "{} {}", entropy, unlimited.1);
+        doea.set_budget(Some(Budget::Time(Duration::ZERO)));
+        assert_eq!(doea.rank_guesses().len(), 1);
+        assert!(doea.guess().is_some());
+    }
+    #[test]
+    fn test_depth_one_entropy_algo_incorporate_external_guess_feedback() {
+        let numbers = generate_default_init_values_for_numbers();
+        let mut doea = DepthOneEntropyAlgo::new(numbers, &Ruleset::classic()).unwrap();
+        assert_eq!(doea.incorporate_external_guess_feedback(String::from("5678"), 0, 0), Some(true));
+        assert_eq!(doea.get_numbers_count(), 360);
+        assert!(doea.get_numbers().iter().all(|number| !number.contains(['5', '6', '7', '8'])));
+        assert_eq!(doea.incorporate_external_guess_feedback(String::from("123"), 0, 0), None);
+        assert_eq!(doea.incorporate_external_guess_feedback(String::from("1234"), 3, 1), None);
+
+        doea.guess().unwrap();
+        assert_eq!(doea.incorporate_external_guess_feedback(String::from("1234"), 0, 0), Some(false));
+    }
+    #[test]
+    fn test_depth_one_entropy_algo_undo_and_revise_feedback() {
+        let numbers = generate_default_init_values_for_numbers();
+        let mut doea = DepthOneEntropyAlgo::new(numbers.clone(), &Ruleset::classic()).unwrap();
+        assert!(!doea.undo_last_guess());
+        let first = doea.guess().unwrap();
+        doea.incorporate_guess_feedback(1, 2).unwrap();
+        let mistyped = doea.get_numbers().to_vec();
+        doea.guess().unwrap();
+        assert_eq!(doea.revise_feedback(1, 0, 0), Some(false));
+        assert_eq!(doea.revise_feedback(0, 5, 0), None);
+
+        // the same numbers as if "2 1" had been typed in the first place
+        assert_eq!(doea.revise_feedback(0, 2, 1), Some(true));
+        let mut expected = DepthOneEntropyAlgo::new(numbers.clone(), &Ruleset::classic()).unwrap();
+        expected.incorporate_external_guess_feedback(first.clone(), 2, 1).unwrap();
+        let pending = doea.past_guesses[1].0.clone();
+        assert_eq!(doea.get_numbers(), expected.get_numbers().iter().filter(|number| **number != pending).cloned().collect::<Vec<String>>());
+        assert!(doea.is_waiting_for_feedback());
+
+        assert!(doea.undo_last_guess());
+        assert_eq!(doea.get_numbers(), expected.get_numbers());
+        assert!(!doea.is_waiting_for_feedback());
+        assert_eq!(doea.revise_feedback(0, 1, 2), Some(true));
+        assert_eq!(doea.get_numbers(), mistyped);
+        assert!(doea.undo_last_guess());
+        assert_eq!(doea.get_numbers(), numbers);
+        assert_eq!(doea.guess(), Some(first));
+    }
 }
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use crate::algos::{algos_utils, Solver};
+use crate::algos::budget::{Budget, BudgetTracker};
+use crate::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+use crate::algos::feedback::FeedbackKind;
+use crate::algos::heuristics::Heuristic;
+use crate::algos::symmetry::Symmetries;
+use crate::ruleset::Ruleset;
+
+// how many of the best depth one guesses are looked at two moves deep
+pub const DEFAULT_WIDTH: usize = 10;
+
+// Looks one move further than `DepthOneEntropyAlgo`: a guess is worth its own entropy plus
+// the expected entropy of the best guess that can follow it, over all the feedbacks it can get.
+// Only the `width` best guesses by depth one entropy are searched, and a guess is dropped as
+// soon as even perfect follow-ups could not beat the best one found so far.
+pub struct DepthTwoEntropyAlgo {
+    // keeps the numbers and the guesses made, the choice of the guesses is done here
+    tracker : DepthOneEntropyAlgo,
+    width : usize,
+    ndigits : usize,
+    // the entropy of a guess never exceeds log2 of its count of possible feedbacks
+    max_entropy : f64,
+    // unlimited by default, shared by the depth one ranking and the search below it
+    budget : Option<Budget>,
+}
+
+impl DepthTwoEntropyAlgo {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset) -> Option<DepthTwoEntropyAlgo> {
+        return DepthTwoEntropyAlgo::new_with_width(numbers, rules, DEFAULT_WIDTH);
+    }
+
+    pub fn new_with_width(numbers: Vec<String>, rules: &Ruleset, width: usize) -> Option<DepthTwoEntropyAlgo> {
+        if width == 0 {
+            return None;
+        }
+        let ndigits: usize = rules.length;
+        // the winning feedback leaves nothing to learn
+        let max_entropy: f64 = ((rules.feedback_kind.get_feedback_count(ndigits) - 1) as f64).log2();
+        let tracker = DepthOneEntropyAlgo::new(numbers, rules)?;
+        return Some(DepthTwoEntropyAlgo {tracker, width, ndigits, max_entropy, budget: None});
+    }
+
+    pub fn guess(&mut self) -> Option<String> {
+        if self.tracker.is_waiting_for_feedback() {
+            return None;
+        }
+        let (best_guess, _) = self.suggest_guess()?;
+        return self.tracker.make_guess(best_guess);
+    }
+
+    pub fn set_budget(&mut self, budget: Option<Budget>) {
+        self.budget = budget;
+    }
+
+    // best guess with the bits expected from it and the guess after it;
+    // when the budget runs out before any guess is searched two moves deep, the best depth one guess with its entropy
+    pub fn suggest_guess(&self) -> Option<(String, f64)> {
+        let mut budget: Option<BudgetTracker> = self.budget.map(BudgetTracker::new);
+        let ranked: Vec<(String, f64)> = self.tracker.rank_guesses_within(budget.as_mut());
+        let fallback: Option<(String, f64)> = ranked.first().cloned();
+        let mut best: Option<(String, f64)> = None;
+        for (guess, entropy) in ranked.into_iter().take(self.width) {
+            if budget.as_ref().is_some_and(|budget| budget.is_exhausted()) {
+                break;
+            }
+            let best_value: f64 = best.as_ref().map_or(f64::MIN, |(_, value)| *value);
+            if let Some(value) = self.calculate_two_step_entropy(&guess, entropy, best_value, budget.as_mut()) {
+                if value > best_value {
+                    best = Some((guess, value));
+                }
+            }
+        }
+        return best.or(fallback);
+    }
+
+    // None when the guess cannot beat `to_beat` or the budget runs out before it is fully searched
+    fn calculate_two_step_entropy(&self, guess: &str, entropy: f64, to_beat: f64, mut budget: Option<&mut BudgetTracker>) -> Option<f64> {
+        let numbers: &[String] = self.tracker.get_numbers();
+        let feedback_kind: FeedbackKind = self.tracker.get_feedback_kind();
+        let win_index: usize = feedback_kind.get_index((self.ndigits, 0), self.ndigits);
+        // by feedback index, only the feedbacks met rather than every positional pattern
+        let mut indexed_branches: HashMap<usize, Vec<&String>> = HashMap::new();
+        for number in numbers.iter() {
+            let bnc: (usize, usize) = feedback_kind.get_feedback(guess, number).unwrap();
+            indexed_branches.entry(feedback_kind.get_index(bnc, self.ndigits)).or_default().push(number);
+        }
+        indexed_branches.remove(&win_index);
+        let left_total: usize = indexed_branches.values().map(|branch| branch.len()).sum();
+        if left_total == 0 {
+            return Some(entropy);
+        }
+        // the biggest branches decide most, search them first
+        let mut indexed_branches: Vec<(usize, Vec<&String>)> = indexed_branches.into_iter().filter(|(_, branch)| branch.len() > 1).collect();
+        indexed_branches.sort_by_key(|(index, branch)| (std::cmp::Reverse(branch.len()), *index));
+        let branches: Vec<Vec<&String>> = indexed_branches.into_iter().map(|(_, branch)| branch).collect();
+
+        // the guesses of a branch that are equivalent once `guess` is made are only scored once
+        let symmetries: Symmetries = self.tracker.get_symmetries(&[guess]);
+
+        // optimistic until the branches are searched
+        let mut upper_bound: f64 = entropy + branches.iter().map(|branch| self.get_branch_weight(branch, left_total) * self.get_entropy_bound(branch)).sum::<f64>();
+        for branch in branches.iter() {
+            if upper_bound <= to_beat {
+                return None;
+            }
+            let bound: f64 = self.get_entropy_bound(branch);
+            let best_entropy: f64 = self.find_best_entropy(branch, bound, &symmetries, budget.as_deref_mut())?;
+            upper_bound -= self.get_branch_weight(branch, left_total) * (bound - best_entropy);
+        }
+        return Some(upper_bound);
+    }
+
+    fn get_branch_weight(&self, branch: &[&String], left_total: usize) -> f64 {
+        return branch.len() as f64 / left_total as f64;
+    }
+
+    // a guess from the branch leaves at most all the other numbers in it, one per feedback
+    fn get_entropy_bound(&self, branch: &[&String]) -> f64 {
+        return ((branch.len() - 1) as f64).log2().min(self.max_entropy);
+    }
+
+    // stops early once a guess reaches `bound`, None when the budget runs out first
+    fn find_best_entropy(&self, branch: &[&String], bound: f64, symmetries: &Symmetries, mut budget: Option<&mut BudgetTracker>) -> Option<f64> {
+        let mut best_entropy: f64 = 0.0;
+        for number in symmetries.get_representatives(branch) {
+            let partition_sizes: Vec<usize> = algos_utils::get_partition_sizes_of_kind(self.tracker.get_feedback_kind(), number, branch, self.ndigits);
+            let entropy: f64 = Heuristic::Entropy.score(&partition_sizes, self.ndigits).unwrap_or(0.0);
+            if let Some(budget) = budget.as_deref_mut() {
+                budget.record_iteration();
+                if budget.is_exhausted() {
+                    return None;
+                }
+            }
+            if entropy > best_entropy {
+                best_entropy = entropy;
+                if best_entropy >= bound - 1e-12 {
+                    break;
+                }
+            }
+        }
+        return Some(best_entropy);
+    }
+
+    pub fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return self.tracker.incorporate_guess_feedback(bulls, cows);
+    }
+
+    pub fn undo_last_guess(&mut self) -> bool {
+        return self.tracker.undo_last_guess();
+    }
+
+    pub fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return self.tracker.revise_feedback(index, bulls, cows);
+    }
+
+    pub fn get_numbers_count(&self) -> usize {
+        return self.tracker.get_numbers_count();
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return self.tracker.get_numbers();
+    }
+}
+
+impl Solver for DepthTwoEntropyAlgo {
+    fn guess(&mut self) -> Option<String> {
+        return DepthTwoEntropyAlgo::guess(self);
+    }
+
+    fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return DepthTwoEntropyAlgo::incorporate_guess_feedback(self, bulls, cows);
+    }
+
+    fn undo_last_guess(&mut self) -> bool {
+        return DepthTwoEntropyAlgo::undo_last_guess(self);
+    }
+
+    fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return DepthTwoEntropyAlgo::revise_feedback(self, index, bulls, cows);
+    }
+
+    fn get_numbers_count(&self) -> usize {
+        return DepthTwoEntropyAlgo::get_numbers_count(self);
+    }
+
+    fn get_numbers(&self) -> &[String] {
+        return DepthTwoEntropyAlgo::get_numbers(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::algos::algos_utils::{generate_init_values_for_numbers, get_bulls_and_cows};
+    use crate::algos::budget::Budget;
+    use super::DepthTwoEntropyAlgo;
+    use crate::ruleset::Ruleset;
+
+    fn three_digits() -> Ruleset {
+        return Ruleset::new(3, &"0123456789".chars().collect::<Vec<char>>(), false);
+    }
+
+    #[test]
+    fn test_depth_two_entropy_algo_new() {
+        assert!(DepthTwoEntropyAlgo::new(Vec::new(), &three_digits()).is_none());
+        assert!(DepthTwoEntropyAlgo::new_with_width(vec![String::from("123")], &three_digits(), 0).is_none());
+        assert!(DepthTwoEntropyAlgo::new(vec![String::from("1234")], &three_digits()).is_none());
+        let dtea = DepthTwoEntropyAlgo::new(generate_init_values_for_numbers(3), &three_digits()).unwrap();
+        assert_eq!(dtea.get_numbers_count(), 720);
+    }
+    #[test]
+    fn test_depth_two_entropy_algo_pruning_keeps_the_best_guess() {
+        // searching every guess without pruning gives the same value
+        let numbers: Vec<String> = generate_init_values_for_numbers(3).into_iter().filter(|number| get_bulls_and_cows(number, "012") == Some((0, 1))).collect();
+        let dtea = DepthTwoEntropyAlgo::new_with_width(numbers.clone(), &three_digits(), numbers.len()).unwrap();
+        let (guess, value) = dtea.suggest_guess().unwrap();
+        let mut best_value: f64 = f64::MIN;
+        for (number, entropy) in dtea.tracker.rank_guesses() {
+            best_value = best_value.max(dtea.calculate_two_step_entropy(&number, entropy, f64::MIN, None).unwrap());
+        }
+        assert!((value - best_value).abs() < 1e-9);
+        assert!(numbers.contains(&guess));
+    }
+    #[test]
+    fn test_depth_two_entropy_algo_solves() {
+        let numbers = generate_init_values_for_numbers(3);
+        for secret in ["012", "987", "360"] {
+            let mut dtea = DepthTwoEntropyAlgo::new(numbers.clone(), &three_digits()).unwrap();
+            let mut solved = false;
+            for _ in 0..8 {
+                let guess = dtea.guess().unwrap();
+                assert_eq!(dtea.guess(), None);
+                let (bulls, cows) = get_bulls_and_cows(&guess, secret).unwrap();
+                if bulls == 3 {
+                    solved = true;
+                    break;
+                }
+                assert_eq!(dtea.incorporate_guess_feedback(bulls, cows), Some(true));
+            }
+            assert!(solved);
+        }
+    }
+    #[test]
+    fn test_depth_two_entropy_algo_with_budget() {
+        let numbers = generate_init_values_for_numbers(3);
+        let unlimited = DepthTwoEntropyAlgo::new(numbers.clone(), &three_digits()).unwrap().suggest_guess().unwrap();
+        let mut dtea = DepthTwoEntropyAlgo::new(numbers.clone(), &three_digits()).unwrap();
+        dtea.set_budget(Some(Budget::Iterations(1_000_000)));
+        assert_eq!(dtea.suggest_guess(), Some(unlimited));
+        // too small to search below any guess, the best depth one guess is played
+        dtea.set_budget(Some(Budget::Iterations(1)));
+        let (guess, _) = dtea.suggest_guess().unwrap();
+        assert!(numbers.contains(&guess));
+        dtea.set_budget(Some(Budget::Time(Duration::from_millis(1))));
+        assert!(dtea.guess().is_some());
+    }
+    #[test]
+    fn test_depth_two_entropy_algo_last_number() {
+        let mut dtea = DepthTwoEntropyAlgo::new(vec![String::from("1234")], &Ruleset::classic()).unwrap();
+        assert_eq!(dtea.guess(), Some(String::from("1234")));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::algos::algos_utils;
+
+// What the keeper tells about a guess. The reduced kinds tell less than bulls and cows and
+// make harder puzzles. Their feedback is still a (bulls, cows) pair so that the solvers
+// partition, index and score it like bulls and cows:
+//   bulls only  (bulls, 0)
+//   total       (0, bulls + cows)
+//   any bull    (1, 0) when some symbol is in its place, (0, 0) otherwise
+// A win is (ndigits, 0) in every kind, the keeper always tells it apart.
+// The positional kind tells more, like Wordle: a mark for every symbol of the guess. Its feedback
+// is (bulls, pattern) with the marks as base 3 digits of the pattern, the first symbol lowest
+// and a bull 0, so that a win is still (ndigits, 0). Its index (see `get_index`) keeps the win
+// where bulls and cows have it and puts the other patterns after every bulls and cows index.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedbackKind {
+    #[default]
+    BullsAndCows,
+    BullsOnly,
+    Total,
+    AnyBull,
+    Positional,
+}
+
+pub const FEEDBACK_KINDS: [FeedbackKind; 5] = [FeedbackKind::BullsAndCows, FeedbackKind::BullsOnly, FeedbackKind::Total, FeedbackKind::AnyBull, FeedbackKind::Positional];
+
+// the patterns fit a u32, longer than any word without a repeated letter
+pub const MAX_POSITIONAL_LENGTH: usize = 20;
+
+// what the positional keeper says about one symbol of the guess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Bull,
+    Cow,
+    Absent,
+}
+
+impl Mark {
+    pub fn to_char(&self) -> char {
+        match self {
+            Mark::Bull => return 'B',
+            Mark::Cow => return 'C',
+            Mark::Absent => return '-',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Mark> {
+        match c.to_ascii_uppercase() {
+            'B' => return Some(Mark::Bull),
+            'C' => return Some(Mark::Cow),
+            '-' => return Some(Mark::Absent),
+            _ => return None,
+        }
+    }
+
+    fn get_digit(&self) -> usize {
+        match self {
+            Mark::Bull => return 0,
+            Mark::Cow => return 1,
+            Mark::Absent => return 2,
+        }
+    }
+}
+
+// the mark of every symbol of the guess, None when the lengths differ; the bulls are marked first,
+// then the other symbols left to right are cows while the secret has copies of them not yet matched,
+// so the cows are the ones of `algos_utils::get_bulls_and_cows` with repeats too
+pub fn get_marks<S0: AsRef<str>, S1: AsRef<str>>(guess: S0, secret: S1) -> Option<Vec<Mark>> {
+    let guess: Vec<char> = guess.as_ref().chars().collect();
+    let secret: Vec<char> = secret.as_ref().chars().collect();
+    if guess.len() != secret.len() {
+        return None;
+    }
+    let mut marks: Vec<Mark> = vec![Mark::Absent; guess.len()];
+    let mut unmatched: Vec<char> = Vec::new();
+    for (i, (&symbol, &secret_symbol)) in guess.iter().zip(secret.iter()).enumerate() {
+        if symbol == secret_symbol {
+            marks[i] = Mark::Bull;
+        } else {
+            unmatched.push(secret_symbol);
+        }
+    }
+    for (i, symbol) in guess.iter().enumerate() {
+        if marks[i] == Mark::Bull {
+            continue;
+        }
+        if let Some(position) = unmatched.iter().position(|other| other == symbol) {
+            unmatched.swap_remove(position);
+            marks[i] = Mark::Cow;
+        }
+    }
+    return Some(marks);
+}
+
+// the positional feedback of the marks
+pub fn encode_marks(marks: &[Mark]) -> (usize, usize) {
+    let bulls: usize = marks.iter().filter(|&&mark| mark == Mark::Bull).count();
+    let pattern: usize = marks.iter().rev().fold(0, |pattern, mark| pattern * 3 + mark.get_digit());
+    return (bulls, pattern);
+}
+
+fn decode_marks(pattern: usize, ndigits: usize) -> Vec<Mark> {
+    let mut marks: Vec<Mark> = Vec::new();
+    let mut left: usize = pattern;
+    for _ in 0..ndigits {
+        marks.push([Mark::Bull, Mark::Cow, Mark::Absent][left % 3]);
+        left /= 3;
+    }
+    return marks;
+}
+
+impl FeedbackKind {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            FeedbackKind::BullsAndCows => return "bulls-and-cows",
+            FeedbackKind::BullsOnly => return "bulls",
+            FeedbackKind::Total => return "total",
+            FeedbackKind::AnyBull => return "any-bull",
+            FeedbackKind::Positional => return "positional",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<FeedbackKind> {
+        return FEEDBACK_KINDS.iter().find(|kind| kind.get_name() == name).copied();
+    }
+
+    // what the keeper of this kind says instead of the bulls and cows,
+    // the positional kind tells more than them and keeps them
+    pub fn reduce(&self, feedback: (usize, usize), ndigits: usize) -> (usize, usize) {
+        let (bulls, cows) = feedback;
+        if bulls == ndigits {
+            return feedback;
+        }
+        match self {
+            FeedbackKind::BullsAndCows | FeedbackKind::Positional => return feedback,
+            FeedbackKind::BullsOnly => return (bulls, 0),
+            FeedbackKind::Total => return (0, bulls + cows),
+            FeedbackKind::AnyBull => return (usize::from(bulls > 0), 0),
+        }
+    }
+
+    // the scorer of this kind, None when the lengths differ
+    pub fn get_feedback<S0: AsRef<str>, S1: AsRef<str>>(&self, guess: S0, secret: S1) -> Option<(usize, usize)> {
+        if *self == FeedbackKind::Positional {
+            let marks: Vec<Mark> = get_marks(guess, secret)?;
+            if marks.len() > MAX_POSITIONAL_LENGTH {
+                return None;
+            }
+            return Some(encode_marks(&marks));
+        }
+        let ndigits: usize = secret.as_ref().chars().count();
+        let feedback: (usize, usize) = algos_utils::get_bulls_and_cows(guess, secret)?;
+        return Some(self.reduce(feedback, ndigits));
+    }
+
+    // the marks of a positional feedback, None for the other kinds
+    pub fn get_marks(&self, feedback: (usize, usize), ndigits: usize) -> Option<Vec<Mark>> {
+        if *self != FeedbackKind::Positional || !self.is_possible(feedback, ndigits) {
+            return None;
+        }
+        return Some(decode_marks(feedback.1, ndigits));
+    }
+
+    // every feedback of this kind, in the order of `algos_utils::generate_possible_feedbacks`,
+    // the positional patterns by increasing pattern with the win last
+    pub fn generate_possible_feedbacks(&self, ndigits: usize) -> Vec<(usize, usize)> {
+        let mut feedbacks: Vec<(usize, usize)> = Vec::new();
+        if *self == FeedbackKind::Positional {
+            for pattern in 1..3_usize.pow(ndigits as u32) {
+                let feedback: (usize, usize) = encode_marks(&decode_marks(pattern, ndigits));
+                if self.is_possible(feedback, ndigits) {
+                    feedbacks.push(feedback);
+                }
+            }
+            feedbacks.push((ndigits, 0));
+            return feedbacks;
+        }
+        for feedback in algos_utils::generate_possible_feedbacks(ndigits) {
+            let reduced: (usize, usize) = self.reduce(feedback, ndigits);
+            if !feedbacks.contains(&reduced) {
+                feedbacks.push(reduced);
+            }
+        }
+        return feedbacks;
+    }
+
+    // how many feedbacks `generate_possible_feedbacks` gives, without listing the positional patterns:
+    // all but the n with a single symbol not a bull and a cow
+    pub fn get_feedback_count(&self, ndigits: usize) -> usize {
+        if *self == FeedbackKind::Positional {
+            return 3_usize.pow(ndigits as u32) - ndigits;
+        }
+        return self.generate_possible_feedbacks(ndigits).len();
+    }
+
+    pub fn is_possible(&self, feedback: (usize, usize), ndigits: usize) -> bool {
+        if *self == FeedbackKind::Positional {
+            if ndigits > MAX_POSITIONAL_LENGTH || feedback.1 >= 3_usize.pow(ndigits as u32) || encode_marks(&decode_marks(feedback.1, ndigits)) != feedback {
+                return false;
+            }
+            // like n - 1 bulls and a cow, a single symbol that is not a bull cannot be a cow
+            let marks: Vec<Mark> = decode_marks(feedback.1, ndigits);
+            return !(feedback.0 + 1 == ndigits && marks.contains(&Mark::Cow));
+        }
+        let (bulls, cows) = feedback;
+        if bulls + cows > ndigits || (ndigits > 0 && bulls == ndigits - 1 && cows == 1) {
+            return false;
+        }
+        match self {
+            FeedbackKind::BullsAndCows => return true,
+            FeedbackKind::BullsOnly => return cows == 0,
+            FeedbackKind::Total => return bulls == 0 || (bulls, cows) == (ndigits, 0),
+            FeedbackKind::AnyBull => return cows == 0 && (bulls <= 1 || bulls == ndigits),
+            FeedbackKind::Positional => return false,
+        }
+    }
+
+    // feedbacks are numbered from 0 to `get_index_count` - 1 to partition and index them cheaply,
+    // bulls and cows by `algos_utils::get_feedback_index`
+    pub fn get_index(&self, feedback: (usize, usize), ndigits: usize) -> usize {
+        if *self == FeedbackKind::Positional && feedback != (ndigits, 0) {
+            return (ndigits + 1) * (ndigits + 1) + feedback.1;
+        }
+        return algos_utils::get_feedback_index(feedback, ndigits);
+    }
+
+    pub fn get_index_count(&self, ndigits: usize) -> usize {
+        if *self == FeedbackKind::Positional {
+            return (ndigits + 1) * (ndigits + 1) + 3_usize.pow(ndigits as u32);
+        }
+        return (ndigits + 1) * (ndigits + 1);
+    }
+
+    // the feedback of an index, the reverse of `get_index`
+    pub fn from_index(&self, index: usize, ndigits: usize) -> (usize, usize) {
+        let cells: usize = (ndigits + 1) * (ndigits + 1);
+        if *self == FeedbackKind::Positional && index >= cells {
+            return encode_marks(&decode_marks(index - cells, ndigits));
+        }
+        return (index / (ndigits + 1), index % (ndigits + 1));
+    }
+
+    // "1b 2c", "1b", "3 found", "a bull", "no bull" or the marks like "BC--", a win is always "Nb 0c"
+    pub fn format(&self, feedback: (usize, usize), ndigits: usize) -> String {
+        let (bulls, cows) = feedback;
+        if bulls == ndigits || *self == FeedbackKind::BullsAndCows {
+            return format!("{}b {}c", bulls, cows);
+        }
+        match self {
+            FeedbackKind::Positional => return decode_marks(cows, ndigits).iter().map(|mark| mark.to_char()).collect(),
+            FeedbackKind::BullsOnly => return format!("{}b", bulls),
+            FeedbackKind::Total => return format!("{} found", cows),
+            _ => return String::from(if bulls > 0 { "a bull" } else { "no bull" }),
+        }
+    }
+
+    // reads what `format` writes and shorter forms: "2" for bulls or a total, "yes" or "no" for any bull,
+    // marks in any case;
+    // bulls and cows as "Nb 0c", "N 0" or "win" always mean a win
+    pub fn parse(&self, text: &str, ndigits: usize) -> Option<(usize, usize)> {
+        let text: &str = text.trim();
+        if text == "win" {
+            return Some((ndigits, 0));
+        }
+        if let Some(feedback) = crate::transcript::parse_feedback(text) {
+            if feedback == (ndigits, 0) || *self == FeedbackKind::BullsAndCows {
+                return Some(feedback);
+            }
+        }
+        let feedback: (usize, usize) = match self {
+            FeedbackKind::BullsAndCows => return None,
+            FeedbackKind::BullsOnly => (text.strip_suffix('b').unwrap_or(text).parse().ok()?, 0),
+            FeedbackKind::Total => (0, text.strip_suffix(" found").unwrap_or(text).parse().ok()?),
+            FeedbackKind::AnyBull => match text {
+                "a bull" | "yes" | "1" => (1, 0),
+                "no bull" | "no" | "0" => (0, 0),
+                _ => return None,
+            },
+            FeedbackKind::Positional => {
+                let marks: Vec<Mark> = text.chars().map(Mark::from_char).collect::<Option<Vec<Mark>>>()?;
+                if marks.len() != ndigits {
+                    return None;
+                }
+                encode_marks(&marks)
+            }
+        };
+        if !self.is_possible(feedback, ndigits) {
+            return None;
+        }
+        return Some(feedback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::algos::algos_utils::{generate_default_init_values_for_numbers, get_bulls_and_cows};
+    use super::{FeedbackKind, FEEDBACK_KINDS};
+
+    #[test]
+    fn test_feedback_kind_names() {
+        for kind in FEEDBACK_KINDS {
+            assert_eq!(FeedbackKind::from_name(kind.get_name()), Some(kind));
+        }
+        assert_eq!(FeedbackKind::from_name("cows"), None);
+    }
+    #[test]
+    fn test_possible_feedbacks_match_the_scorer() {
+        let numbers = generate_default_init_values_for_numbers();
+        for kind in FEEDBACK_KINDS {
+            let possible = kind.generate_possible_feedbacks(4);
+            let mut seen: HashSet<(usize, usize)> = HashSet::new();
+            for number in numbers.iter() {
+                let feedback = kind.get_feedback("0123", number).unwrap();
+                assert!(possible.contains(&feedback), "{:?} {:?}", kind, feedback);
+                assert!(kind.is_possible(feedback, 4));
+                seen.insert(feedback);
+            }
+            assert_eq!(seen.len(), possible.len(), "{:?}", kind);
+            assert!(possible.iter().all(|&feedback| kind.is_possible(feedback, 4)));
+        }
+        assert_eq!(FeedbackKind::BullsAndCows.generate_possible_feedbacks(4).len(), 14);
+        assert_eq!(FeedbackKind::BullsOnly.generate_possible_feedbacks(4), vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]);
+        // four found without a win is "0123" against "1032"
+        assert_eq!(FeedbackKind::Total.generate_possible_feedbacks(4), vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (4, 0)]);
+        assert_eq!(FeedbackKind::AnyBull.generate_possible_feedbacks(4), vec![(0, 0), (1, 0), (4, 0)]);
+        assert!(!FeedbackKind::Total.is_possible((1, 2), 4));
+        assert!(!FeedbackKind::AnyBull.is_possible((2, 0), 4));
+        // every pattern but the 4 with three bulls and a cow
+        assert_eq!(FeedbackKind::Positional.generate_possible_feedbacks(4).len(), 81 - 4);
+        for kind in FEEDBACK_KINDS {
+            for ndigits in 1..7 {
+                assert_eq!(kind.get_feedback_count(ndigits), kind.generate_possible_feedbacks(ndigits).len(), "{:?} {}", kind, ndigits);
+            }
+        }
+    }
+    #[test]
+    fn test_positional_marks() {
+        use super::{encode_marks, get_marks, Mark};
+        assert_eq!(get_marks("1234", "4231"), Some(vec![Mark::Cow, Mark::Bull, Mark::Bull, Mark::Cow]));
+        assert_eq!(get_marks("1234", "123"), None);
+        // repeated symbols are cows only while the secret has copies left, bulls first
+        assert_eq!(get_marks("RRGG", "GRBB"), Some(vec![Mark::Absent, Mark::Bull, Mark::Cow, Mark::Absent]));
+        for (guess, secret) in [("RRGG", "GRBB"), ("1122", "2211"), ("RGBY", "YRRG")] {
+            let marks = get_marks(guess, secret).unwrap();
+            let cows: usize = marks.iter().filter(|&&mark| mark == Mark::Cow).count();
+            assert_eq!(get_bulls_and_cows(guess, secret).unwrap().1, cows);
+        }
+        assert_eq!(encode_marks(&[Mark::Bull; 4]), (4, 0));
+        assert_eq!(FeedbackKind::Positional.get_feedback("1234", "1234"), Some((4, 0)));
+
+        let kind = FeedbackKind::Positional;
+        let mut indices: HashSet<usize> = HashSet::new();
+        for feedback in kind.generate_possible_feedbacks(4) {
+            let index: usize = kind.get_index(feedback, 4);
+            assert!(index < kind.get_index_count(4) && indices.insert(index));
+            assert_eq!(kind.from_index(index, 4), feedback);
+        }
+        assert_eq!(kind.get_index((4, 0), 4), FeedbackKind::BullsAndCows.get_index((4, 0), 4));
+        assert_eq!(kind.get_marks((1, 0), 4), None);
+        assert_eq!(FeedbackKind::BullsAndCows.get_marks((4, 0), 4), None);
+        // longer patterns would overflow
+        let long: String = "0".repeat(super::MAX_POSITIONAL_LENGTH + 1);
+        assert_eq!(kind.get_feedback(&long, &long), None);
+        assert!(!kind.is_possible((long.len(), 0), long.len()));
+    }
+    #[test]
+    fn test_reduced_feedback_keeps_the_win() {
+        assert_eq!(get_bulls_and_cows("1234", "4321"), Some((0, 4)));
+        assert_eq!(FeedbackKind::Total.get_feedback("1234", "4321"), Some((0, 4)));
+        assert_eq!(FeedbackKind::Total.get_feedback("1234", "1234"), Some((4, 0)));
+        assert_eq!(FeedbackKind::AnyBull.get_feedback("1234", "1243"), Some((1, 0)));
+        assert_eq!(FeedbackKind::BullsOnly.get_feedback("1234", "1243"), Some((2, 0)));
+    }
+    #[test]
+    fn test_format_and_parse() {
+        for kind in FEEDBACK_KINDS {
+            for feedback in kind.generate_possible_feedbacks(4) {
+                assert_eq!(kind.parse(&kind.format(feedback, 4), 4), Some(feedback), "{:?}", kind);
+            }
+            assert_eq!(kind.parse("win", 4), Some((4, 0)));
+            assert_eq!(kind.parse("4 0", 4), Some((4, 0)));
+            assert_eq!(kind.parse("nonsense", 4), None);
+        }
+        assert_eq!(FeedbackKind::Total.format((0, 3), 4), "3 found");
+        assert_eq!(FeedbackKind::Total.parse("3", 4), Some((0, 3)));
+        assert_eq!(FeedbackKind::BullsOnly.parse("2", 4), Some((2, 0)));
+        assert_eq!(FeedbackKind::AnyBull.parse("yes", 4), Some((1, 0)));
+        assert_eq!(FeedbackKind::AnyBull.format((0, 0), 4), "no bull");
+        let feedback = FeedbackKind::Positional.parse("bc--", 4).unwrap();
+        assert_eq!(FeedbackKind::Positional.format(feedback, 4), "BC--");
+        assert_eq!(FeedbackKind::Positional.parse("BBBC", 4), None);
+        assert_eq!(FeedbackKind::Positional.parse("BC-", 4), None);
+    }
+}
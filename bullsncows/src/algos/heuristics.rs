@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use crate::algos::algos_utils::get_feedback_index;
+
+// Ways to rate a guess from how it splits the remaining numbers by feedback.
+// Every score is "higher is better" so that the solvers can simply take the maximum.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Heuristic {
+    // information gained in bits
+    Entropy,
+    // expected count of numbers left after the guess, a win leaves none
+    ExpectedSize,
+    // count of distinct feedbacks the guess can get
+    MostParts,
+    // largest count of numbers that can be left after the guess
+    WorstCase,
+}
+
+pub const HEURISTICS: [Heuristic; 4] = [Heuristic::Entropy, Heuristic::ExpectedSize, Heuristic::MostParts, Heuristic::WorstCase];
+
+impl Heuristic {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Heuristic::Entropy => return "entropy",
+            Heuristic::ExpectedSize => return "expected-size",
+            Heuristic::MostParts => return "most-parts",
+            Heuristic::WorstCase => return "worst-case",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Heuristic> {
+        return HEURISTICS.iter().find(|heuristic| heuristic.get_name() == name).copied();
+    }
+
+    // `partition_sizes` as returned by `algos_utils::get_partition_sizes`,
+    // None when the guess is the only number left and there is nothing to rate
+    pub fn score(&self, partition_sizes: &[usize], ndigits: usize) -> Option<f64> {
+        let win_index: usize = get_feedback_index((ndigits, 0), ndigits);
+        let total: usize = partition_sizes.iter().sum();
+        let left_sizes = partition_sizes.iter().enumerate().filter(|&(i, &size)| i != win_index && size > 0).map(|(_, &size)| size);
+        let left_total: usize = total - partition_sizes[win_index];
+        if left_total == 0 {
+            return None;
+        }
+
+        match self {
+            Heuristic::Entropy => {
+                let mut entropy: f64 = 0.0;
+                for size in left_sizes {
+                    let probability: f64 = size as f64 / left_total as f64;
+                    entropy += -probability * probability.log2();
+                }
+                return Some(entropy);
+            }
+            Heuristic::ExpectedSize => {
+                let squares: usize = left_sizes.map(|size| size * size).sum();
+                return Some(-(squares as f64) / total as f64);
+            }
+            Heuristic::MostParts => {
+                return Some(partition_sizes.iter().filter(|&&size| size > 0).count() as f64);
+            }
+            Heuristic::WorstCase => return Some(-(left_sizes.max().unwrap() as f64)),
+        }
+    }
+}
+
+// Cheap ordering for searches that may not score every guess: a symbol tells the most when it is
+// in about half of the numbers, and at a position in about half of them, so the guesses made of
+// the most uncertain symbols come first. Guesses of the same promise keep their order.
+pub fn order_promising_first<'a>(guesses: Vec<&'a String>, numbers: &[String]) -> Vec<&'a String> {
+    let total: f64 = numbers.len().max(1) as f64;
+    let mut present_counts: HashMap<char, usize> = HashMap::new();
+    let mut position_counts: HashMap<(usize, char), usize> = HashMap::new();
+    for number in numbers.iter() {
+        let mut symbols: Vec<char> = Vec::new();
+        for (position, symbol) in number.chars().enumerate() {
+            *position_counts.entry((position, symbol)).or_insert(0) += 1;
+            if !symbols.contains(&symbol) {
+                symbols.push(symbol);
+            }
+        }
+        for symbol in symbols {
+            *present_counts.entry(symbol).or_insert(0) += 1;
+        }
+    }
+    let uncertainty = |count: Option<&usize>| {
+        let frequency: f64 = *count.unwrap_or(&0) as f64 / total;
+        frequency * (1.0 - frequency)
+    };
+
+    let mut keyed: Vec<(f64, &'a String)> = guesses.into_iter().map(|guess| {
+        let mut promise: f64 = 0.0;
+        let mut symbols: Vec<char> = Vec::new();
+        for (position, symbol) in guess.chars().enumerate() {
+            promise += uncertainty(position_counts.get(&(position, symbol)));
+            if !symbols.contains(&symbol) {
+                promise += uncertainty(present_counts.get(&symbol));
+                symbols.push(symbol);
+            }
+        }
+        (promise, guess)
+    }).collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    return keyed.into_iter().map(|(_, guess)| guess).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{order_promising_first, Heuristic, HEURISTICS};
+    use crate::algos::algos_utils::get_partition_sizes;
+
+    #[test]
+    fn test_heuristic_names() {
+        for heuristic in HEURISTICS {
+            assert_eq!(Heuristic::from_name(heuristic.get_name()), Some(heuristic));
+        }
+        assert_eq!(Heuristic::from_name("baseline"), None);
+    }
+    #[test]
+    fn test_heuristic_scores() {
+        // guessing 1234: win, 2b 2c, 0b 4c, 0b 4c
+        let numbers = vec!["1234", "1243", "4321", "2143"];
+        let sizes = get_partition_sizes("1234", &numbers, 4);
+        let entropy = Heuristic::Entropy.score(&sizes, 4).unwrap();
+        assert!((entropy - (3.0f64.log2() - 2.0 / 3.0)).abs() < 1e-9);
+        assert_eq!(Heuristic::ExpectedSize.score(&sizes, 4), Some(-5.0 / 4.0));
+        assert_eq!(Heuristic::MostParts.score(&sizes, 4), Some(3.0));
+        assert_eq!(Heuristic::WorstCase.score(&sizes, 4), Some(-2.0));
+
+        let sizes = get_partition_sizes("1234", &["1234"], 4);
+        for heuristic in HEURISTICS {
+            assert_eq!(heuristic.score(&sizes, 4), None);
+        }
+    }
+    #[test]
+    fn test_order_promising_first() {
+        // 1 and 2 are in every number and 7 and 8 in none, 3 to 6 are in half of them
+        let numbers = vec![String::from("1234"), String::from("1256"), String::from("2134"), String::from("2156")];
+        let guess = String::from("1278");
+        let ordered = order_promising_first(vec![&guess, &numbers[0], &numbers[1]], &numbers);
+        assert_eq!(ordered, vec![&numbers[0], &numbers[1], &guess]);
+    }
+}
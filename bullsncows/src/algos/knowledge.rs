@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::algos::{algos_utils, Solver};
+use crate::ruleset::Ruleset;
+
+// What the remaining candidates say about the secret, symbol by symbol.
+// Everything here is derived from the candidate set only, so it is exactly as
+// strong as the filtering done by the solver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Knowledge {
+    pub ndigits: usize,
+    pub candidates_count: usize,
+    // symbols present in every candidate
+    pub present: BTreeSet<char>,
+    // symbols of the alphabet present in no candidate
+    pub absent: BTreeSet<char>,
+    // positions each symbol occupies in at least one candidate, symbols in `absent` are left out
+    pub possible_positions: BTreeMap<char, BTreeSet<usize>>,
+}
+
+impl Knowledge {
+    // the candidates are secrets of the rules, the symbols of their alphabet in none of them are absent
+    pub fn from_candidates(numbers: &[String], rules: &Ruleset) -> Option<Knowledge> {
+        if !algos_utils::fit_rules(numbers, rules) {
+            return None;
+        }
+        let ndigits: usize = rules.length;
+
+        let mut possible_positions: BTreeMap<char, BTreeSet<usize>> = BTreeMap::new();
+        let mut present: BTreeSet<char> = numbers[0].chars().collect();
+        for number in numbers.iter() {
+            for (position, symbol) in number.chars().enumerate() {
+                possible_positions.entry(symbol).or_default().insert(position);
+            }
+            present.retain(|symbol| number.contains(*symbol));
+        }
+        let absent: BTreeSet<char> = rules.alphabet.iter().filter(|symbol| !possible_positions.contains_key(symbol)).copied().collect();
+
+        return Some(Knowledge { ndigits, candidates_count: numbers.len(), present, absent, possible_positions });
+    }
+
+    pub fn from_solver(solver: &dyn Solver, rules: &Ruleset) -> Option<Knowledge> {
+        return Knowledge::from_candidates(solver.get_numbers(), rules);
+    }
+
+    pub fn get_possible_symbols(&self, position: usize) -> BTreeSet<char> {
+        return self.possible_positions.iter()
+            .filter(|(_, positions)| positions.contains(&position))
+            .map(|(symbol, _)| *symbol)
+            .collect();
+    }
+
+    // positions where only one symbol is still possible
+    pub fn get_confirmed_positions(&self) -> Vec<(usize, char)> {
+        let mut confirmed: Vec<(usize, char)> = Vec::new();
+        for position in 0..self.ndigits {
+            let symbols = self.get_possible_symbols(position);
+            if symbols.len() == 1 {
+                confirmed.push((position, *symbols.iter().next().unwrap()));
+            }
+        }
+        return confirmed;
+    }
+
+    pub fn is_solved(&self) -> bool {
+        return self.candidates_count == 1;
+    }
+
+    // one line per fact, positions are 1-based for humans
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        lines.push(format!("{} possible secret{}", self.candidates_count, if self.candidates_count == 1 { "" } else { "s" }));
+        if !self.present.is_empty() {
+            lines.push(format!("certainly in the secret: {}", join_symbols(self.present.iter())));
+        }
+        if !self.absent.is_empty() {
+            lines.push(format!("certainly not in the secret: {}", join_symbols(self.absent.iter())));
+        }
+        for position in 0..self.ndigits {
+            let symbols = self.get_possible_symbols(position);
+            if symbols.len() == 1 {
+                lines.push(format!("position {} is {}", position + 1, symbols.iter().next().unwrap()));
+            } else {
+                lines.push(format!("position {} is one of {}", position + 1, join_symbols(symbols.iter())));
+            }
+        }
+        return lines;
+    }
+
+    // how the guess relates to what is known, e.g. to explain a suggestion
+    pub fn explain_guess(&self, guess: &str) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        let guess_symbols: Vec<char> = guess.chars().collect();
+
+        let confirmed: Vec<String> = guess_symbols.iter().enumerate()
+            .filter(|(position, symbol)| self.get_confirmed_positions().contains(&(*position, **symbol)))
+            .map(|(position, symbol)| format!("{} at position {}", symbol, position + 1))
+            .collect();
+        if !confirmed.is_empty() {
+            parts.push(format!("keeps the confirmed {}", confirmed.join(", ")));
+        }
+        let present: BTreeSet<char> = guess_symbols.iter().filter(|symbol| self.present.contains(symbol)).copied().collect();
+        if !present.is_empty() {
+            parts.push(format!("uses {} known to be in the secret", join_symbols(present.iter())));
+        }
+        let unknown: BTreeSet<char> = guess_symbols.iter().filter(|symbol| !self.present.contains(symbol) && !self.absent.contains(symbol)).copied().collect();
+        if !unknown.is_empty() {
+            parts.push(format!("tests {} which may or may not be in the secret", join_symbols(unknown.iter())));
+        }
+        let absent: BTreeSet<char> = guess_symbols.iter().filter(|symbol| self.absent.contains(symbol)).copied().collect();
+        if !absent.is_empty() {
+            parts.push(format!("fills in with {} known to be absent", join_symbols(absent.iter())));
+        }
+        let impossible: Vec<String> = guess_symbols.iter().enumerate()
+            .filter(|(position, symbol)| !self.absent.contains(symbol) && !self.possible_positions.get(symbol).is_some_and(|positions| positions.contains(position)))
+            .map(|(position, symbol)| format!("{} at position {}", symbol, position + 1))
+            .collect();
+        if !impossible.is_empty() {
+            let pronoun = if impossible.len() == 1 { ("it", "is") } else { ("they", "are") };
+            parts.push(format!("tries {}, where {} {} ruled out, to narrow down where else {} can be", impossible.join(", "), pronoun.0, pronoun.1, pronoun.0));
+        }
+        return format!("{} {}", guess, parts.join("; "));
+    }
+}
+
+fn join_symbols<'a, I: Iterator<Item = &'a char>>(symbols: I) -> String {
+    return symbols.map(|symbol| symbol.to_string()).collect::<Vec<String>>().join(" ");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Knowledge;
+    use crate::ruleset::Ruleset;
+
+    #[test]
+    fn test_knowledge_from_candidates() {
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("1534")];
+        let knowledge = Knowledge::from_candidates(&numbers, &Ruleset::classic()).unwrap();
+        assert_eq!(knowledge.ndigits, 4);
+        assert_eq!(knowledge.candidates_count, 3);
+        assert_eq!(knowledge.present, ['1', '3', '4'].into_iter().collect());
+        assert_eq!(knowledge.absent, ['0', '6', '7', '8', '9'].into_iter().collect());
+        assert_eq!(knowledge.possible_positions[&'3'], [2, 3].into_iter().collect());
+        assert_eq!(knowledge.possible_positions[&'5'], [1].into_iter().collect());
+        assert_eq!(knowledge.get_possible_symbols(1), ['2', '5'].into_iter().collect());
+        assert_eq!(knowledge.get_confirmed_positions(), vec![(0, '1')]);
+        assert!(!knowledge.is_solved());
+
+        assert!(Knowledge::from_candidates(&[], &Ruleset::classic()).is_none());
+        assert!(Knowledge::from_candidates(&[String::from("123")], &Ruleset::classic()).is_none());
+    }
+    #[test]
+    fn test_knowledge_with_repeats() {
+        let numbers = vec![String::from("1123"), String::from("1132")];
+        let knowledge = Knowledge::from_candidates(&numbers, &Ruleset { repeats: true, ..Ruleset::classic() }).unwrap();
+        assert_eq!(knowledge.present, ['1', '2', '3'].into_iter().collect());
+        assert_eq!(knowledge.possible_positions[&'1'], [0, 1].into_iter().collect());
+        assert_eq!(knowledge.get_confirmed_positions(), vec![(0, '1'), (1, '1')]);
+    }
+    #[test]
+    fn test_knowledge_from_solver() {
+        use crate::algos::baseline_algo::BaselineAlgo;
+        use crate::algos::algos_utils::generate_default_init_values_for_numbers;
+        let mut ba = BaselineAlgo::new_with_seed(generate_default_init_values_for_numbers(), &Ruleset::classic(), 1).unwrap();
+        let guess = ba.guess().unwrap();
+        ba.incorporate_guess_feedback(0, 0).unwrap();
+        let knowledge = Knowledge::from_solver(&ba, &Ruleset::classic()).unwrap();
+        for symbol in guess.chars() {
+            assert!(knowledge.absent.contains(&symbol));
+        }
+        assert_eq!(knowledge.candidates_count, 360);
+        assert_eq!(knowledge.present.len(), 0);
+    }
+    #[test]
+    fn test_knowledge_describe_and_explain() {
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("1534")];
+        let knowledge = Knowledge::from_candidates(&numbers, &Ruleset::classic()).unwrap();
+        let lines = knowledge.describe();
+        assert_eq!(lines[0], "3 possible secrets");
+        assert_eq!(lines[1], "certainly in the secret: 1 3 4");
+        assert_eq!(lines[2], "certainly not in the secret: 0 6 7 8 9");
+        assert_eq!(lines[3], "position 1 is 1");
+        assert_eq!(lines[4], "position 2 is one of 2 5");
+
+        let explanation = knowledge.explain_guess("1534");
+        assert!(explanation.starts_with("1534 keeps the confirmed 1 at position 1"));
+        assert!(explanation.contains("uses 1 3 4 known to be in the secret"));
+        assert!(explanation.contains("tests 5 which may or may not be in the secret"));
+        let explanation = knowledge.explain_guess("4109");
+        assert!(explanation.contains("fills in with 0 9 known to be absent"));
+        assert_eq!(explanation, "4109 uses 1 4 known to be in the secret; fills in with 0 9 known to be absent; tries 4 at position 1, 1 at position 2, where they are ruled out, to narrow down where else they can be");
+        let explanation = knowledge.explain_guess("5234");
+        assert!(explanation.ends_with("; tries 5 at position 1, where it is ruled out, to narrow down where else it can be"));
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use crate::algos::Solver;
+use crate::algos::budget::{Budget, BudgetTracker};
+use crate::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+use crate::ruleset::Ruleset;
+use crate::algos::feedback::FeedbackKind;
+
+pub const DEFAULT_ITERATIONS: usize = 2000;
+// weight of the exploration term of UCB, in guesses
+const EXPLORATION: f64 = 1.4;
+
+// Monte Carlo tree search: every iteration samples a secret from the remaining numbers,
+// walks down the tree choosing guesses with UCB, adds one node and finishes the game with
+// random consistent guesses. The guess tried most often at the root is played.
+// Guesses are restricted to consistent numbers, one per class of equivalent guesses.
+pub struct MctsAlgo {
+    // keeps the numbers and the guesses made, the choice of the guesses is done here
+    tracker : DepthOneEntropyAlgo,
+    rng : StdRng,
+    budget : Budget,
+}
+
+struct Node {
+    numbers : Vec<String>,
+    // guesses made from the root to here
+    path : Vec<String>,
+    // created on the first visit
+    actions : Option<Vec<Action>>,
+    visits : u64,
+}
+
+struct Action {
+    guess : String,
+    visits : u64,
+    // guesses needed from the node on, summed over the visits
+    total_guesses : u64,
+    // node index by feedback
+    children : HashMap<(usize, usize), usize>,
+}
+
+impl Action {
+    // lower is better, unvisited actions come first
+    fn get_ucb_cost(&self, parent_visits: u64) -> f64 {
+        if self.visits == 0 {
+            return f64::MIN;
+        }
+        let mean: f64 = self.total_guesses as f64 / self.visits as f64;
+        return mean - EXPLORATION * ((parent_visits as f64).ln() / self.visits as f64).sqrt();
+    }
+}
+
+impl MctsAlgo {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset) -> Option<MctsAlgo> {
+        return MctsAlgo::new_with_rng(numbers, rules, StdRng::from_entropy(), Budget::Iterations(DEFAULT_ITERATIONS));
+    }
+
+    // the same seed, numbers and iteration budget always give the same sequence of guesses
+    pub fn new_with_seed(numbers: Vec<String>, rules: &Ruleset, seed: u64, budget: Budget) -> Option<MctsAlgo> {
+        return MctsAlgo::new_with_rng(numbers, rules, StdRng::seed_from_u64(seed), budget);
+    }
+
+    fn new_with_rng(numbers: Vec<String>, rules: &Ruleset, rng: StdRng, budget: Budget) -> Option<MctsAlgo> {
+        let tracker = DepthOneEntropyAlgo::new(numbers, rules)?;
+        return Some(MctsAlgo {tracker, rng, budget});
+    }
+
+    pub fn guess(&mut self) -> Option<String> {
+        if self.tracker.is_waiting_for_feedback() {
+            return None;
+        }
+        let (best_guess, _) = self.search()?;
+        return self.tracker.make_guess(best_guess);
+    }
+
+    // best guess with the average guesses it needed in the simulations, without committing to it
+    pub fn search(&mut self) -> Option<(String, f64)> {
+        let numbers: Vec<String> = self.tracker.get_numbers().to_vec();
+        if numbers.len() <= 1 {
+            return numbers.into_iter().next().map(|number| (number, 1.0));
+        }
+
+        let mut nodes: Vec<Node> = vec![Node {numbers, path: Vec::new(), actions: None, visits: 0}];
+        let mut budget = BudgetTracker::new(self.budget);
+        // at least one iteration so that there is a guess to return
+        loop {
+            self.run_iteration(&mut nodes);
+            budget.record_iteration();
+            if budget.is_exhausted() {
+                break;
+            }
+        }
+
+        let actions: &Vec<Action> = nodes[0].actions.as_ref()?;
+        let best: &Action = actions.iter().filter(|action| action.visits > 0)
+            .max_by(|a, b| a.visits.cmp(&b.visits).then((b.total_guesses * a.visits).cmp(&(a.total_guesses * b.visits))))?;
+        return Some((best.guess.clone(), best.total_guesses as f64 / best.visits as f64));
+    }
+
+    fn run_iteration(&mut self, nodes: &mut Vec<Node>) {
+        let secret: String = nodes[0].numbers.choose(&mut self.rng).unwrap().clone();
+        let feedback_kind: FeedbackKind = self.tracker.get_feedback_kind();
+        // (node, action) pairs taken, a node's depth is its index here
+        let mut path: Vec<(usize, usize)> = Vec::new();
+        let mut node_index: usize = 0;
+        let total_guesses: u64 = loop {
+            if nodes[node_index].actions.is_none() {
+                let actions: Vec<Action> = self.create_actions(&nodes[node_index]);
+                nodes[node_index].actions = Some(actions);
+            }
+            let node: &Node = &nodes[node_index];
+            let actions: &Vec<Action> = node.actions.as_ref().unwrap();
+            let action_index: usize = (0..actions.len())
+                .min_by(|&a, &b| actions[a].get_ucb_cost(node.visits).total_cmp(&actions[b].get_ucb_cost(node.visits)))
+                .unwrap();
+            path.push((node_index, action_index));
+
+            let guess: &str = &actions[action_index].guess;
+            if guess == secret {
+                break path.len() as u64;
+            }
+            let feedback: (usize, usize) = feedback_kind.get_feedback(guess, &secret).unwrap();
+            match actions[action_index].children.get(&feedback) {
+                Some(&child) => node_index = child,
+                None => {
+                    let numbers: Vec<String> = node.numbers.iter()
+                        .filter(|number| number.as_str() != guess && feedback_kind.get_feedback(guess, number) == Some(feedback))
+                        .cloned().collect();
+                    let mut child_path: Vec<String> = node.path.clone();
+                    child_path.push(String::from(guess));
+                    let rollout_guesses: u64 = self.rollout(&numbers, &secret);
+                    nodes.push(Node {numbers, path: child_path, actions: None, visits: 0});
+                    let child: usize = nodes.len() - 1;
+                    nodes[node_index].actions.as_mut().unwrap()[action_index].children.insert(feedback, child);
+                    break path.len() as u64 + rollout_guesses;
+                }
+            }
+        };
+
+        for (depth, &(node_index, action_index)) in path.iter().enumerate() {
+            let node: &mut Node = &mut nodes[node_index];
+            node.visits += 1;
+            let action: &mut Action = &mut node.actions.as_mut().unwrap()[action_index];
+            action.visits += 1;
+            action.total_guesses += total_guesses - depth as u64;
+        }
+    }
+
+    // the consistent numbers, one per class of equivalent guesses, in random order
+    fn create_actions(&mut self, node: &Node) -> Vec<Action> {
+        let path: Vec<&str> = node.path.iter().map(|guess| guess.as_str()).collect();
+        let symmetries = self.tracker.get_symmetries(&path);
+        let mut guesses: Vec<&String> = symmetries.get_representatives(&node.numbers);
+        guesses.shuffle(&mut self.rng);
+        return guesses.into_iter().map(|guess| Action {guess: guess.clone(), visits: 0, total_guesses: 0, children: HashMap::new()}).collect();
+    }
+
+    // guesses a random consistent number until the secret is found
+    fn rollout(&mut self, numbers: &[String], secret: &str) -> u64 {
+        let mut numbers: Vec<&String> = numbers.iter().collect();
+        let mut guesses: u64 = 0;
+        let feedback_kind: FeedbackKind = self.tracker.get_feedback_kind();
+        loop {
+            let guess: &String = numbers.choose(&mut self.rng).unwrap();
+            guesses += 1;
+            if guess == secret {
+                return guesses;
+            }
+            let feedback = feedback_kind.get_feedback(guess, secret);
+            numbers.retain(|number| feedback_kind.get_feedback(guess, number) == feedback && *number != guess);
+        }
+    }
+
+    pub fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return self.tracker.incorporate_guess_feedback(bulls, cows);
+    }
+
+    pub fn undo_last_guess(&mut self) -> bool {
+        return self.tracker.undo_last_guess();
+    }
+
+    pub fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return self.tracker.revise_feedback(index, bulls, cows);
+    }
+
+    pub fn get_numbers_count(&self) -> usize {
+        return self.tracker.get_numbers_count();
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return self.tracker.get_numbers();
+    }
+}
+
+impl Solver for MctsAlgo {
+    fn guess(&mut self) -> Option<String> {
+        return MctsAlgo::guess(self);
+    }
+
+    fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return MctsAlgo::incorporate_guess_feedback(self, bulls, cows);
+    }
+
+    fn undo_last_guess(&mut self) -> bool {
+        return MctsAlgo::undo_last_guess(self);
+    }
+
+    fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return MctsAlgo::revise_feedback(self, index, bulls, cows);
+    }
+
+    fn get_numbers_count(&self) -> usize {
+        return MctsAlgo::get_numbers_count(self);
+    }
+
+    fn get_numbers(&self) -> &[String] {
+        return MctsAlgo::get_numbers(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::algos::algos_utils::{generate_init_values_for_numbers, get_bulls_and_cows};
+    use crate::algos::budget::Budget;
+    use super::MctsAlgo;
+    use crate::ruleset::Ruleset;
+
+    fn three_digits() -> Ruleset {
+        return Ruleset::new(3, &"0123456789".chars().collect::<Vec<char>>(), false);
+    }
+
+    fn play(mcts: &mut MctsAlgo, secret: &str) -> Option<usize> {
+        for guesses in 1..=10 {
+            let guess = mcts.guess()?;
+            let (bulls, cows) = get_bulls_and_cows(&guess, secret).unwrap();
+            if bulls == secret.len() {
+                return Some(guesses);
+            }
+            mcts.incorporate_guess_feedback(bulls, cows)?;
+        }
+        return None;
+    }
+
+    #[test]
+    fn test_mcts_algo_solves() {
+        let numbers = generate_init_values_for_numbers(3);
+        for (i, secret) in ["012", "987", "360"].iter().enumerate() {
+            let mut mcts = MctsAlgo::new_with_seed(numbers.clone(), &three_digits(), i as u64, Budget::Iterations(300)).unwrap();
+            assert!(play(&mut mcts, secret).is_some());
+        }
+        let mut mcts = MctsAlgo::new_with_seed(vec![String::from("1234")], &Ruleset::classic(), 0, Budget::Iterations(1)).unwrap();
+        assert_eq!(mcts.guess(), Some(String::from("1234")));
+        assert_eq!(mcts.guess(), None);
+    }
+    #[test]
+    fn test_mcts_algo_with_seed_is_reproducible() {
+        let numbers = generate_init_values_for_numbers(3);
+        let mut first = MctsAlgo::new_with_seed(numbers.clone(), &three_digits(), 7, Budget::Iterations(200)).unwrap();
+        let mut second = MctsAlgo::new_with_seed(numbers, &three_digits(), 7, Budget::Iterations(200)).unwrap();
+        for _ in 0..3 {
+            let guess = first.guess().unwrap();
+            assert_eq!(second.guess(), Some(guess.clone()));
+            let (bulls, cows) = get_bulls_and_cows(&guess, "456").unwrap();
+            if bulls == 3 {
+                break;
+            }
+            first.incorporate_guess_feedback(bulls, cows).unwrap();
+            second.incorporate_guess_feedback(bulls, cows).unwrap();
+        }
+    }
+    #[test]
+    fn test_mcts_algo_prefers_informative_guesses() {
+        // 1243 tells the other numbers apart, 5678 only tells itself apart
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("1324"), String::from("2134"), String::from("5678")];
+        let mut mcts = MctsAlgo::new_with_seed(numbers, &Ruleset::classic(), 1, Budget::Iterations(2000)).unwrap();
+        let (guess, average) = mcts.search().unwrap();
+        assert!(guess == "1243" || guess == "2134", "{}", guess);
+        assert!(average < 2.0);
+
+        let mut mcts = MctsAlgo::new_with_seed(generate_init_values_for_numbers(3), &three_digits(), 1, Budget::Time(Duration::from_millis(20))).unwrap();
+        assert!(mcts.guess().is_some());
+    }
+}
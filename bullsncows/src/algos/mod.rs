@@ -1,3 +1,119 @@
 pub mod algos_utils;
 pub mod baseline_algo;
-pub mod depth_one_entropy_algo;
\ No newline at end of file
+pub mod batch;
+pub mod budget;
+pub mod depth_one_entropy_algo;
+pub mod depth_two_entropy_algo;
+pub mod feedback;
+pub mod heuristics;
+pub mod knowledge;
+pub mod mcts_algo;
+pub mod multi_secret_algo;
+pub mod optimal_algo;
+pub mod separating_set;
+pub mod symmetry;
+
+use baseline_algo::BaselineAlgo;
+use budget::Budget;
+use depth_one_entropy_algo::DepthOneEntropyAlgo;
+use depth_two_entropy_algo::DepthTwoEntropyAlgo;
+use heuristics::Heuristic;
+use mcts_algo::MctsAlgo;
+use optimal_algo::OptimalAlgo;
+use crate::ruleset::Ruleset;
+
+// common interface of the guessing algorithms, so that the game loop can use any of them
+pub trait Solver {
+    fn guess(&mut self) -> Option<String>;
+    fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool>;
+    // forgets the last guess and its feedback if any, false when no guess was made
+    fn undo_last_guess(&mut self) -> bool;
+    // corrects the feedback of an answered guess (counted from 0), the numbers are recomputed from the original ones;
+    // None for impossible feedback, Some(false) when there is no such answered guess
+    fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool>;
+    fn get_numbers_count(&self) -> usize;
+    // numbers still consistent with all the feedback, without the pending guess
+    fn get_numbers(&self) -> &[String];
+}
+
+pub const SOLVER_NAMES: &[&str] = &["baseline", "entropy", "entropy-2", "expected-size", "most-parts", "worst-case", "optimal", "mcts"];
+
+// a solver for the secrets of the rules, expecting their kind of feedback; seed and budget are only used
+// by the solvers that need them, the mcts solver has a default budget and the others search until done without one
+pub fn create_solver(name: &str, rules: &Ruleset, seed: Option<u64>, budget: Option<Budget>) -> Option<Box<dyn Solver>> {
+    rules.validate().ok()?;
+    let numbers = rules.generate_numbers();
+    match name {
+        "baseline" => {
+            let solver = match seed {
+                Some(seed) => BaselineAlgo::new_with_seed(numbers, rules, seed),
+                None => BaselineAlgo::new(numbers, rules),
+            }?;
+            return Some(Box::new(solver));
+        }
+        "entropy-2" => {
+            let mut solver = DepthTwoEntropyAlgo::new(numbers, rules)?;
+            solver.set_budget(budget);
+            return Some(Box::new(solver));
+        }
+        "optimal" => return OptimalAlgo::new(numbers, rules).map(|s| Box::new(s) as Box<dyn Solver>),
+        "mcts" => {
+            let budget = budget.unwrap_or(Budget::Iterations(mcts_algo::DEFAULT_ITERATIONS));
+            let solver = match seed {
+                Some(seed) => MctsAlgo::new_with_seed(numbers, rules, seed, budget),
+                None => MctsAlgo::new_with_seed(numbers, rules, rand::random(), budget),
+            }?;
+            return Some(Box::new(solver));
+        }
+        _ => {
+            let heuristic = Heuristic::from_name(name)?;
+            let mut solver = DepthOneEntropyAlgo::new_with_heuristic(numbers, rules, heuristic)?;
+            solver.set_budget(budget);
+            return Some(Box::new(solver));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::feedback::FEEDBACK_KINDS;
+    use crate::ruleset::Ruleset;
+
+    #[test]
+    fn test_create_solver() {
+        use super::{create_solver, SOLVER_NAMES};
+        let rules = Ruleset::new(2, &['1', '2', '3'], false);
+        for name in SOLVER_NAMES {
+            let mut solver = create_solver(name, &rules, Some(1), None).unwrap();
+            assert_eq!(solver.get_numbers_count(), 6);
+            assert!(solver.guess().is_some());
+        }
+        assert!(create_solver("unknown", &rules, None, None).is_none());
+        assert!(create_solver("entropy", &Ruleset::new(4, &['1', '2', '3'], false), None, None).is_none());
+    }
+    #[test]
+    fn test_solvers_play_reduced_feedback() {
+        use super::{create_solver, SOLVER_NAMES};
+        // small enough for the optimal search with the least telling kinds
+        for kind in FEEDBACK_KINDS {
+            let rules = Ruleset { feedback_kind: kind, ..Ruleset::new(3, &['0', '1', '2', '3', '4'], false) };
+            for name in SOLVER_NAMES {
+                for secret in ["012", "432", "403"] {
+                    let mut solver = create_solver(name, &rules, Some(1), None).unwrap();
+                    let mut guesses: usize = 0;
+                    loop {
+                        let guess = solver.guess().unwrap();
+                        guesses += 1;
+                        let (bulls, cows) = kind.get_feedback(&guess, secret).unwrap();
+                        if bulls == 3 {
+                            break;
+                        }
+                        assert_eq!(solver.incorporate_guess_feedback(bulls, cows), Some(true));
+                        assert!(solver.get_numbers().iter().any(|number| number == secret), "{} {:?}", name, kind);
+                    }
+                    assert!(guesses as u64 <= rules.get_numbers_count());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+use crate::algos::algos_utils;
+use crate::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+use crate::algos::feedback::FeedbackKind;
+use crate::algos::symmetry::Symmetries;
+use crate::ruleset::Ruleset;
+
+// Guesses for a game against several secrets at once (see `multi::MultiGame`): every board keeps
+// its own candidates in a `DepthOneEntropyAlgo`, filtered like a single game. The secrets are
+// independent, so the information a guess gives about all of them is the sum of what it gives
+// about each board. A board left with a single candidate is finished first, it costs one guess anyway.
+pub struct MultiSecretAlgo {
+    trackers : Vec<DepthOneEntropyAlgo>,
+    solved : Vec<bool>,
+    ndigits : usize,
+    feedback_kind : FeedbackKind,
+    // the guess waiting for its feedback
+    pending : Option<String>,
+}
+
+impl MultiSecretAlgo {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset, boards: usize) -> Option<MultiSecretAlgo> {
+        if boards == 0 {
+            return None;
+        }
+        let mut trackers: Vec<DepthOneEntropyAlgo> = Vec::new();
+        for _ in 0..boards {
+            trackers.push(DepthOneEntropyAlgo::new(numbers.clone(), rules)?);
+        }
+        return Some(MultiSecretAlgo {trackers, solved: vec![false; boards], ndigits: rules.length, feedback_kind: rules.feedback_kind, pending: None});
+    }
+
+    pub fn guess(&mut self) -> Option<String> {
+        if self.pending.is_some() {
+            return None;
+        }
+        let (best_guess, _) = self.suggest_guess()?;
+        self.pending = Some(best_guess.clone());
+        return Some(best_guess);
+    }
+
+    // best guess with the bits it is expected to tell about all the unsolved boards, without committing to it;
+    // None once every board is solved or when a board has no candidate left
+    pub fn suggest_guess(&self) -> Option<(String, f64)> {
+        let unsolved: Vec<&DepthOneEntropyAlgo> = self.get_unsolved_trackers();
+        if unsolved.is_empty() || unsolved.iter().any(|tracker| tracker.get_numbers_count() == 0) {
+            return None;
+        }
+        if let Some(tracker) = unsolved.iter().find(|tracker| tracker.get_numbers_count() == 1) {
+            let guess: &String = &tracker.get_numbers()[0];
+            return Some((guess.clone(), self.calculate_combined_entropy(guess)));
+        }
+
+        let mut guesses: Vec<&String> = Vec::new();
+        let mut seen: HashSet<&String> = HashSet::new();
+        for tracker in unsolved.iter() {
+            for number in tracker.get_numbers() {
+                if seen.insert(number) {
+                    guesses.push(number);
+                }
+            }
+        }
+        // every unsolved board has seen every guess, their symmetries are the same
+        let symmetries: Symmetries = unsolved[0].get_symmetries(&[]);
+        let mut best: Option<(String, f64)> = None;
+        for &guess in symmetries.get_representatives(&guesses) {
+            let entropy: f64 = self.calculate_combined_entropy(guess);
+            if best.as_ref().is_none_or(|(_, best_entropy)| entropy > *best_entropy) {
+                best = Some((guess.clone(), entropy));
+            }
+        }
+        return best;
+    }
+
+    // the feedback of the pending guess on every board, None for the boards solved before it;
+    // None when the feedbacks do not fit the boards or leave an unsolved board without candidates,
+    // Some(false) without a pending guess
+    pub fn incorporate_guess_feedback(&mut self, feedbacks: &[Option<(usize, usize)>]) -> Option<bool> {
+        if feedbacks.len() != self.trackers.len() {
+            return None;
+        }
+        for (board, feedback) in feedbacks.iter().enumerate() {
+            if feedback.is_some() == self.solved[board] || feedback.is_some_and(|feedback| !self.feedback_kind.is_possible(feedback, self.ndigits)) {
+                return None;
+            }
+        }
+        let guess: String = match self.pending.take() {
+            Some(guess) => guess,
+            None => return Some(false),
+        };
+        for (board, feedback) in feedbacks.iter().enumerate() {
+            let (bulls, cows) = match feedback {
+                Some(feedback) => *feedback,
+                None => continue,
+            };
+            if (bulls, cows) == (self.ndigits, 0) {
+                self.solved[board] = true;
+            } else if self.trackers[board].incorporate_external_guess_feedback(guess.clone(), bulls, cows) != Some(true) {
+                return None;
+            }
+        }
+        if self.get_unsolved_trackers().iter().any(|tracker| tracker.get_numbers_count() == 0) {
+            return None;
+        }
+        return Some(true);
+    }
+
+    pub fn get_boards_count(&self) -> usize {
+        return self.trackers.len();
+    }
+
+    pub fn is_solved(&self, board: usize) -> bool {
+        return self.solved[board];
+    }
+
+    // numbers of the board still consistent with its feedback, without the pending guess
+    pub fn get_numbers(&self, board: usize) -> &[String] {
+        return self.trackers[board].get_numbers();
+    }
+
+    fn get_unsolved_trackers(&self) -> Vec<&DepthOneEntropyAlgo> {
+        return self.trackers.iter().zip(self.solved.iter()).filter(|(_, &solved)| !solved).map(|(tracker, _)| tracker).collect();
+    }
+
+    // a win tells something about a board too: that it is done
+    fn calculate_combined_entropy(&self, guess: &str) -> f64 {
+        let mut entropy: f64 = 0.0;
+        for tracker in self.get_unsolved_trackers() {
+            let partition_sizes: Vec<usize> = algos_utils::get_partition_sizes_of_kind(self.feedback_kind, guess, tracker.get_numbers(), self.ndigits);
+            let total: f64 = tracker.get_numbers_count() as f64;
+            for &size in partition_sizes.iter().filter(|&&size| size > 0) {
+                let probability: f64 = size as f64 / total;
+                entropy -= probability * probability.log2();
+            }
+        }
+        return entropy;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::algos_utils::{generate_init_values_for_numbers, get_bulls_and_cows};
+    use super::MultiSecretAlgo;
+    use crate::ruleset::Ruleset;
+
+    fn three_digits() -> Ruleset {
+        return Ruleset::new(3, &"0123456789".chars().collect::<Vec<char>>(), false);
+    }
+
+    fn play(solver: &mut MultiSecretAlgo, secrets: &[&str]) -> usize {
+        let mut solved: Vec<bool> = vec![false; secrets.len()];
+        let mut guesses: usize = 0;
+        while solved.contains(&false) {
+            let guess = solver.guess().unwrap();
+            guesses += 1;
+            let feedbacks: Vec<Option<(usize, usize)>> = secrets.iter().zip(solved.iter()).map(|(secret, &solved)| {
+                if solved { None } else { get_bulls_and_cows(&guess, secret) }
+            }).collect();
+            for (board, feedback) in feedbacks.iter().enumerate() {
+                if *feedback == Some((3, 0)) {
+                    solved[board] = true;
+                }
+            }
+            assert_eq!(solver.incorporate_guess_feedback(&feedbacks), Some(true));
+            for (board, secret) in secrets.iter().enumerate() {
+                assert!(solved[board] || solver.get_numbers(board).iter().any(|number| number == secret));
+            }
+        }
+        assert!(solver.guess().is_none());
+        return guesses;
+    }
+
+    #[test]
+    fn test_multi_secret_algo_solves_every_board() {
+        let numbers = generate_init_values_for_numbers(3);
+        let secrets = ["012", "987", "503", "246"];
+        let mut solver = MultiSecretAlgo::new(numbers.clone(), &three_digits(), secrets.len()).unwrap();
+        let guesses = play(&mut solver, &secrets);
+        // at least one guess per board, far fewer than solving them one after the other
+        assert!(guesses >= secrets.len() && guesses <= 14, "{}", guesses);
+        assert!((0..4).all(|board| solver.is_solved(board)));
+
+        // the same secret on two boards is solved by one guess
+        let mut solver = MultiSecretAlgo::new(numbers, &three_digits(), 2).unwrap();
+        play(&mut solver, &["123", "123"]);
+    }
+    #[test]
+    fn test_multi_secret_algo_feedback() {
+        let mut solver = MultiSecretAlgo::new(generate_init_values_for_numbers(3), &three_digits(), 2).unwrap();
+        assert!(MultiSecretAlgo::new(Vec::new(), &three_digits(), 2).is_none());
+        assert!(MultiSecretAlgo::new(generate_init_values_for_numbers(3), &three_digits(), 0).is_none());
+        assert_eq!(solver.incorporate_guess_feedback(&[Some((0, 0)), Some((0, 0))]), Some(false));
+        let guess = solver.guess().unwrap();
+        assert!(solver.guess().is_none());
+        assert_eq!(solver.incorporate_guess_feedback(&[Some((0, 0))]), None);
+        assert_eq!(solver.incorporate_guess_feedback(&[Some((3, 0)), None]), None);
+        assert_eq!(solver.incorporate_guess_feedback(&[Some((3, 0)), Some((0, 1))]), Some(true));
+        assert!(solver.is_solved(0));
+        assert!(!solver.get_numbers(1).contains(&guess));
+        solver.guess().unwrap();
+        assert_eq!(solver.incorporate_guess_feedback(&[None, Some((3, 0))]), Some(true));
+        assert!(solver.guess().is_none());
+
+        // contradicting feedback leaves the board without candidates
+        let mut solver = MultiSecretAlgo::new(generate_init_values_for_numbers(3), &three_digits(), 1).unwrap();
+        let guess = solver.guess().unwrap();
+        assert_eq!(solver.incorporate_guess_feedback(&[Some((0, 3))]), Some(true));
+        let next_guess = solver.guess().unwrap();
+        assert!(guess.chars().all(|symbol| next_guess.contains(symbol)));
+        assert_eq!(solver.incorporate_guess_feedback(&[Some((0, 0))]), None);
+        assert!(solver.guess().is_none());
+    }
+}
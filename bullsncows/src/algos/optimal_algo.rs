@@ -0,0 +1,579 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use crate::algos::{algos_utils, symmetry, Solver};
+use crate::algos::symmetry::{SymbolClasses, Symmetries};
+use crate::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+use crate::algos::feedback::FeedbackKind;
+use crate::ruleset::Ruleset;
+
+// the searches that finish: the 1296 codes of Mastermind take a few seconds, the 5040 numbers of
+// the classic game about an hour
+pub const MAX_OPTIMAL_NUMBERS: usize = 5_040;
+
+// the guesses made give the symmetries of the game up to this many, after that the symbols
+// interchangeable in the numbers left are cheaper to find and tell more guesses apart
+const MAX_SYMMETRIC_HISTORY: usize = 3;
+
+// Exhaustive branch and bound search for the strategy with the fewest guesses on average.
+// Numbers are referred to by their index, the feedback of every pair is computed once,
+// results are memoized per set of remaining numbers and only one guess of every class of
+// equivalent ones (see `symmetry`) is searched.
+pub struct OptimalSearch {
+    numbers : Vec<String>,
+    // set when the numbers are every number over an alphabet, the game is then symmetric
+    universe_alphabet : Option<Vec<char>>,
+    ndigits : usize,
+    // see `algos_utils::build_feedback_table`
+    feedbacks : Vec<u8>,
+    // the same by secret then guess
+    feedbacks_by_secret : Vec<u8>,
+    // secrets that can be told apart by one guess, without the guessed one
+    max_parts : u64,
+    candidates_only : bool,
+    feedback_kind : FeedbackKind,
+    memo : HashMap<Vec<u32>, MemoEntry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum MemoEntry {
+    // total guesses with the best first guess
+    Exact(u64, u32),
+    // no strategy needs fewer guesses in total
+    AtLeast(u64),
+}
+
+// tells equivalent guesses apart, see `OptimalSearch::is_untried_class`
+enum GuessClasses {
+    History(Symmetries),
+    Symbols(SymbolClasses),
+}
+
+impl GuessClasses {
+    fn canonical_guess(&self, guess: &str) -> String {
+        return match self {
+            GuessClasses::History(symmetries) => symmetries.canonical_guess(guess),
+            GuessClasses::Symbols(classes) => classes.canonical_guess(guess),
+        };
+    }
+}
+
+// the guesses tried on a set, to try one guess of every class of equivalent ones
+struct TriedClasses {
+    // found for the second guess, most searches end with the first
+    classes : Option<GuessClasses>,
+    first_guess : Option<u32>,
+    canonical_guesses : HashSet<String>,
+    // guesses splitting the set into the same parts are as good as each other
+    partitions : HashSet<Vec<u8>>,
+}
+
+// the strategy found, the guess to make and what to do after each feedback other than a win
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionTree {
+    pub guess : String,
+    pub secrets : usize,
+    // sum of the guesses needed for every secret
+    pub total_guesses : u64,
+    pub branches : Vec<((usize, usize), DecisionTree)>,
+    // what the feedback of the branches tells
+    pub feedback_kind : FeedbackKind,
+}
+
+impl DecisionTree {
+    pub fn format_feedback(&self, feedback: (usize, usize)) -> String {
+        return self.feedback_kind.format(feedback, self.guess.chars().count());
+    }
+
+    pub fn get_average_guesses(&self) -> f64 {
+        return self.total_guesses as f64 / self.secrets as f64;
+    }
+
+    // guesses to make against a secret, following the tree
+    pub fn get_guesses_for(&self, secret: &str) -> Option<Vec<String>> {
+        let mut guesses: Vec<String> = vec![self.guess.clone()];
+        let mut node: &DecisionTree = self;
+        while node.guess != secret {
+            let feedback = self.feedback_kind.get_feedback(&node.guess, secret)?;
+            node = &node.branches.iter().find(|(branch_feedback, _)| *branch_feedback == feedback)?.1;
+            guesses.push(node.guess.clone());
+        }
+        return Some(guesses);
+    }
+
+    // one line per node, indented by depth: "1b 2c -> 4567 (3 secrets)", the feedback as its kind writes it
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = vec![format!("{} ({} secrets)", self.guess, self.secrets)];
+        self.describe_branches(1, &mut lines);
+        return lines;
+    }
+
+    fn describe_branches(&self, depth: usize, lines: &mut Vec<String>) {
+        for (feedback, child) in self.branches.iter() {
+            lines.push(format!("{}{} -> {} ({} secrets)", "  ".repeat(depth), self.format_feedback(*feedback), child.guess, child.secrets));
+            child.describe_branches(depth + 1, lines);
+        }
+    }
+}
+
+impl OptimalSearch {
+    // the strategy for the numbers, secrets of the rules, against a keeper telling the feedback of the rules;
+    // with `candidates_only` the guesses are restricted to numbers that can still be the secret
+    pub fn new(numbers: Vec<String>, rules: &Ruleset, candidates_only: bool) -> Option<OptimalSearch> {
+        if !algos_utils::fit_rules(&numbers, rules) || numbers.len() > MAX_OPTIMAL_NUMBERS {
+            return None;
+        }
+        let ndigits: usize = rules.length;
+        let feedback_kind: FeedbackKind = rules.feedback_kind;
+        let feedbacks: Vec<u8> = algos_utils::build_feedback_table(&numbers, feedback_kind)?;
+        let feedbacks_by_secret: Vec<u8> = (0..numbers.len() * numbers.len()).map(|i| feedbacks[i % numbers.len() * numbers.len() + i / numbers.len()]).collect();
+        let universe_alphabet: Option<Vec<char>> = symmetry::get_universe_alphabet(&numbers, rules);
+        let max_parts: u64 = (feedback_kind.get_feedback_count(ndigits) - 1) as u64;
+        return Some(OptimalSearch {numbers, universe_alphabet, ndigits, feedbacks, feedbacks_by_secret, max_parts, candidates_only, feedback_kind, memo: HashMap::new()});
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return &self.numbers;
+    }
+
+    // the best strategy against every number
+    pub fn solve(&mut self) -> DecisionTree {
+        let all: Vec<u32> = (0..self.numbers.len() as u32).collect();
+        return self.build_tree(&all, &[]);
+    }
+
+    // `set` holds sorted indices of the remaining numbers, `history` the indices of the guesses made so far
+    pub fn find_best_guess(&mut self, set: &[u32], history: &[u32]) -> Option<(u64, u32)> {
+        if set.is_empty() {
+            return None;
+        }
+        return self.search(set, history, u64::MAX).ok();
+    }
+
+    fn build_tree(&mut self, set: &[u32], history: &[u32]) -> DecisionTree {
+        let (total_guesses, guess) = self.find_best_guess(set, history).unwrap();
+        let history: Vec<u32> = [history, &[guess]].concat();
+        let mut branches: Vec<((usize, usize), DecisionTree)> = Vec::new();
+        for (feedback_index, part) in self.partition(guess, set).into_iter().enumerate() {
+            if part.is_empty() {
+                continue;
+            }
+            let feedback = self.feedback_kind.from_index(feedback_index, self.ndigits);
+            branches.push((feedback, self.build_tree(&part, &history)));
+        }
+        return DecisionTree {guess: self.numbers[guess as usize].clone(), secrets: set.len(), total_guesses, branches, feedback_kind: self.feedback_kind};
+    }
+
+    // the numbers of the set by feedback to the guess, the guess itself is left out
+    fn partition(&self, guess: u32, set: &[u32]) -> Vec<Vec<u32>> {
+        let mut parts: Vec<Vec<u32>> = vec![Vec::new(); self.feedback_kind.get_index_count(self.ndigits)];
+        let row: &[u8] = &self.feedbacks[guess as usize * self.numbers.len()..(guess as usize + 1) * self.numbers.len()];
+        for &secret in set.iter() {
+            if secret != guess {
+                parts[row[secret as usize] as usize].push(secret);
+            }
+        }
+        return parts;
+    }
+
+    fn get_symbol_classes(&self, set: &[u32]) -> SymbolClasses {
+        return match &self.universe_alphabet {
+            Some(alphabet) => {
+                let numbers: Vec<&str> = set.iter().map(|&number| self.numbers[number as usize].as_str()).collect();
+                SymbolClasses::new(alphabet, &numbers)
+            }
+            None => SymbolClasses::new(&[], &[] as &[&str]),
+        };
+    }
+
+    fn get_symmetries(&self, history: &[u32]) -> Symmetries {
+        return match &self.universe_alphabet {
+            Some(alphabet) => {
+                let past_guesses: Vec<&str> = history.iter().map(|&guess| self.numbers[guess as usize].as_str()).collect();
+                Symmetries::new_for_kind(alphabet, self.ndigits, &past_guesses, self.feedback_kind)
+            }
+            None => Symmetries::none(),
+        };
+    }
+
+    // secrets found with exactly d guesses are at most max_parts^(d - 1), fill the depths in order
+    fn lower_bound(&self, count: usize) -> u64 {
+        let mut bound: u64 = 0;
+        let mut left: u64 = count as u64;
+        let mut depth: u64 = 1;
+        let mut width: u64 = 1;
+        while left > 0 {
+            let solved: u64 = width.min(left);
+            bound += solved * depth;
+            left -= solved;
+            depth += 1;
+            width = width.saturating_mul(self.max_parts);
+        }
+        return bound;
+    }
+
+    // a guess that cannot win finds every secret later, at best spread evenly over the feedbacks
+    fn lower_bound_without_win(&self, count: usize) -> u64 {
+        let part: usize = count / self.max_parts as usize;
+        let larger_parts: u64 = (count % self.max_parts as usize) as u64;
+        return count as u64 + (self.max_parts - larger_parts) * self.lower_bound(part) + larger_parts * self.lower_bound(part + 1);
+    }
+
+    // calls `add` with the position of the guess and the feedback index for every guess against every number of the set,
+    // with every number as a guess the feedbacks are read secret by secret, in order
+    fn for_each_feedback(&self, set: &[u32], guesses: &[u32], mut add: impl FnMut(usize, usize)) {
+        let count: usize = self.numbers.len();
+        if guesses.len() == count {
+            for &secret in set.iter() {
+                for (guess, &index) in self.feedbacks_by_secret[secret as usize * count..(secret as usize + 1) * count].iter().enumerate() {
+                    add(guess, index as usize);
+                }
+            }
+            return;
+        }
+        for (i, &guess) in guesses.iter().enumerate() {
+            let row: &[u8] = &self.feedbacks[guess as usize * count..(guess as usize + 1) * count];
+            for &secret in set.iter() {
+                add(i, row[secret as usize] as usize);
+            }
+        }
+    }
+
+    // the lower bound of the total of every guess on the set and whether it cannot win, None for a guess that
+    // tells nothing apart
+    fn get_guess_bounds(&self, set: &[u32], guesses: &[u32]) -> Vec<Option<(u64, bool)>> {
+        let index_count: usize = self.feedback_kind.get_index_count(self.ndigits);
+        let win_index: usize = self.feedback_kind.get_index((self.ndigits, 0), self.ndigits);
+        let n: u64 = set.len() as u64;
+        if n <= self.max_parts + 1 && index_count <= 64 {
+            // every part is of at most max_parts numbers, of bound 2c - 1: the bound is 3n - the parts,
+            // 2 less for a guess that can win, and only which feedbacks are given matters
+            let mut given: Vec<u64> = vec![0; guesses.len()];
+            self.for_each_feedback(set, guesses, |i, index| given[i] |= 1 << index);
+            return given.into_iter().map(|given| {
+                let wins: u64 = (given >> win_index) & 1;
+                let parts: u64 = given.count_ones() as u64 - wins;
+                return if wins == 0 && parts == 1 { None } else { Some((3 * n - 2 * wins - parts, wins == 0)) };
+            }).collect();
+        }
+        let part_bounds: Vec<u64> = (0..=set.len()).map(|size| self.lower_bound(size)).collect();
+        let mut sizes: Vec<u32> = vec![0; guesses.len() * index_count];
+        self.for_each_feedback(set, guesses, |i, index| sizes[i * index_count + index] += 1);
+        return sizes.chunks(index_count).map(|sizes| {
+            let cannot_win: bool = sizes[win_index] == 0;
+            if cannot_win && sizes.contains(&(n as u32)) {
+                return None;
+            }
+            let parts_bound: u64 = sizes.iter().enumerate().filter(|(index, _)| *index != win_index).map(|(_, &size)| part_bounds[size as usize]).sum();
+            return Some((n + parts_bound, cannot_win));
+        }).collect();
+    }
+
+    // the guesses worth trying on the set, lower bound of their total below `limit` and whether they cannot win,
+    // with the lowest bound of the guesses left out
+    fn get_options(&self, set: &[u32], limit: u64) -> (Vec<(u64, bool, u32)>, u64) {
+        let mut left_out_bound: u64 = u64::MAX;
+        let guesses: Vec<u32> = if self.candidates_only {
+            set.to_vec()
+        } else if self.lower_bound_without_win(set.len()) >= limit {
+            // only the numbers of the set can be below the limit
+            left_out_bound = self.lower_bound_without_win(set.len());
+            set.to_vec()
+        } else {
+            (0..self.numbers.len() as u32).collect()
+        };
+        let mut options: Vec<(u64, bool, u32)> = Vec::new();
+        for (guess, bound) in guesses.iter().zip(self.get_guess_bounds(set, &guesses)) {
+            match bound {
+                Some((bound, _)) if bound >= limit => left_out_bound = left_out_bound.min(bound),
+                Some((bound, cannot_win)) => options.push((bound, cannot_win, *guess)),
+                None => {}
+            }
+        }
+        return (options, left_out_bound);
+    }
+
+    // whether no guess of the class of the guess was tried on the set yet, the guess then counts as tried
+    fn is_untried_class(&self, tried: &mut TriedClasses, set: &[u32], history: &[u32], guess: u32) -> bool {
+        let first_guess: u32 = match tried.first_guess {
+            Some(first_guess) => first_guess,
+            None => {
+                tried.first_guess = Some(guess);
+                return true;
+            }
+        };
+        if tried.classes.is_none() {
+            // early in the game the guesses made give the symmetries, later the symbols left interchangeable do
+            tried.classes = Some(if history.len() <= MAX_SYMMETRIC_HISTORY {
+                GuessClasses::History(self.get_symmetries(history))
+            } else {
+                GuessClasses::Symbols(self.get_symbol_classes(set))
+            });
+            self.add_tried_class(tried, set, first_guess);
+        }
+        return self.add_tried_class(tried, set, guess);
+    }
+
+    fn add_tried_class(&self, tried: &mut TriedClasses, set: &[u32], guess: u32) -> bool {
+        let canonical_guess: String = tried.classes.as_ref().unwrap().canonical_guess(&self.numbers[guess as usize]);
+        let row: &[u8] = &self.feedbacks[guess as usize * self.numbers.len()..(guess as usize + 1) * self.numbers.len()];
+        return tried.canonical_guesses.insert(canonical_guess) && tried.partitions.insert(set.iter().map(|&secret| row[secret as usize]).collect());
+    }
+
+    // total guesses and best first guess for the set, or a lower bound of the total when it cannot be below `limit`
+    fn search(&mut self, set: &[u32], history: &[u32], limit: u64) -> Result<(u64, u32), u64> {
+        // guessing one of two numbers finds the other next, too many sets are that small to remember them
+        if set.len() <= 2 {
+            let total: u64 = self.lower_bound(set.len());
+            return if total < limit { Ok((total, set[0])) } else { Err(total) };
+        }
+        let count_bound: u64 = self.lower_bound(set.len());
+        if count_bound >= limit {
+            return Err(count_bound);
+        }
+        match self.memo.get(set) {
+            Some(MemoEntry::Exact(total, guess)) => return if *total < limit { Ok((*total, *guess)) } else { Err(*total) },
+            Some(MemoEntry::AtLeast(bound)) if *bound >= limit => return Err(*bound),
+            _ => {}
+        }
+
+        let (mut options, mut failed_bound) = self.get_options(set, limit);
+        options.sort_by_key(|(bound, cannot_win, guess)| (*bound, *cannot_win, *guess));
+        let mut best: Option<(u64, u32)> = None;
+        let mut best_total: u64 = limit;
+        let mut tried = TriedClasses {classes: None, first_guess: None, canonical_guesses: HashSet::new(), partitions: HashSet::new()};
+        for (bound, _, guess) in options {
+            if bound >= best_total {
+                failed_bound = failed_bound.min(bound);
+                break;
+            }
+            if !self.is_untried_class(&mut tried, set, history, guess) {
+                continue;
+            }
+            let next_history: Vec<u32> = [history, &[guess]].concat();
+            let mut parts: Vec<Vec<u32>> = self.partition(guess, set);
+            parts.retain(|part| !part.is_empty());
+            parts.sort_by_key(|part| Reverse(part.len()));
+            let mut total: u64 = bound;
+            let mut complete: bool = true;
+            for part in parts.iter() {
+                let part_bound: u64 = self.lower_bound(part.len());
+                match self.search(part, &next_history, best_total - (total - part_bound)) {
+                    Ok((part_total, _)) => total += part_total - part_bound,
+                    Err(part_total) => {
+                        // the part alone takes the guess to the limit or beyond
+                        total += part_total - part_bound;
+                        complete = false;
+                        break;
+                    }
+                }
+            }
+            if complete && total < best_total {
+                best_total = total;
+                best = Some((total, guess));
+            } else {
+                failed_bound = failed_bound.min(total);
+            }
+        }
+
+        // without a strategy below the limit, no guess does better than its bound
+        let entry = match best {
+            Some((total, guess)) => MemoEntry::Exact(total, guess),
+            None => MemoEntry::AtLeast(failed_bound.max(limit)),
+        };
+        self.memo.insert(set.to_vec(), entry);
+        return best.ok_or(failed_bound.max(limit));
+    }
+}
+
+// plays the optimal strategy, searching it on the fly and remembering what was searched
+pub struct OptimalAlgo {
+    tracker : DepthOneEntropyAlgo,
+    search : OptimalSearch,
+    indices : HashMap<String, u32>,
+    history : Vec<u32>,
+}
+
+impl OptimalAlgo {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset) -> Option<OptimalAlgo> {
+        let search = OptimalSearch::new(numbers.clone(), rules, false)?;
+        let indices: HashMap<String, u32> = numbers.iter().enumerate().map(|(i, number)| (number.clone(), i as u32)).collect();
+        let tracker = DepthOneEntropyAlgo::new(numbers, rules)?;
+        return Some(OptimalAlgo {tracker, search, indices, history: Vec::new()});
+    }
+
+    pub fn guess(&mut self) -> Option<String> {
+        if self.tracker.is_waiting_for_feedback() {
+            return None;
+        }
+        let mut set: Vec<u32> = self.tracker.get_numbers().iter().map(|number| self.indices[number]).collect();
+        set.sort();
+        let (_, guess) = self.search.find_best_guess(&set, &self.history)?;
+        self.history.push(guess);
+        let guess: String = self.search.get_numbers()[guess as usize].clone();
+        return self.tracker.make_guess(guess);
+    }
+
+    pub fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return self.tracker.incorporate_guess_feedback(bulls, cows);
+    }
+
+    pub fn undo_last_guess(&mut self) -> bool {
+        if !self.tracker.undo_last_guess() {
+            return false;
+        }
+        self.history.pop();
+        return true;
+    }
+
+    pub fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return self.tracker.revise_feedback(index, bulls, cows);
+    }
+
+    pub fn get_numbers_count(&self) -> usize {
+        return self.tracker.get_numbers_count();
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return self.tracker.get_numbers();
+    }
+}
+
+impl Solver for OptimalAlgo {
+    fn guess(&mut self) -> Option<String> {
+        return OptimalAlgo::guess(self);
+    }
+
+    fn incorporate_guess_feedback(&mut self, bulls: usize, cows: usize) -> Option<bool> {
+        return OptimalAlgo::incorporate_guess_feedback(self, bulls, cows);
+    }
+
+    fn undo_last_guess(&mut self) -> bool {
+        return OptimalAlgo::undo_last_guess(self);
+    }
+
+    fn revise_feedback(&mut self, index: usize, bulls: usize, cows: usize) -> Option<bool> {
+        return OptimalAlgo::revise_feedback(self, index, bulls, cows);
+    }
+
+    fn get_numbers_count(&self) -> usize {
+        return OptimalAlgo::get_numbers_count(self);
+    }
+
+    fn get_numbers(&self) -> &[String] {
+        return OptimalAlgo::get_numbers(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::algos_utils::get_bulls_and_cows;
+    use super::{OptimalAlgo, OptimalSearch};
+    use crate::ruleset::Ruleset;
+
+    // plain minimum over every guess, without bounds, memo or symmetry
+    fn brute_force_total(numbers: &[String], set: &[String], candidates_only: bool) -> u64 {
+        if set.len() == 1 {
+            return 1;
+        }
+        let guesses: &[String] = if candidates_only { set } else { numbers };
+        let mut best: u64 = u64::MAX;
+        for guess in guesses.iter() {
+            let mut parts: std::collections::BTreeMap<(usize, usize), Vec<String>> = std::collections::BTreeMap::new();
+            for secret in set.iter().filter(|secret| *secret != guess) {
+                parts.entry(get_bulls_and_cows(guess, secret).unwrap()).or_default().push(secret.clone());
+            }
+            if parts.len() == 1 && parts.values().next().unwrap().len() == set.len() {
+                continue;
+            }
+            let total: u64 = set.len() as u64 + parts.values().map(|part| brute_force_total(numbers, part, candidates_only)).sum::<u64>();
+            best = best.min(total);
+        }
+        return best;
+    }
+
+    #[test]
+    fn test_optimal_search_matches_brute_force() {
+        for (alphabet, ndigits, repeats) in [("0123", 2, false), ("0123", 3, false), ("012", 2, true), ("01234", 2, false)] {
+            let rules = Ruleset::new(ndigits, &alphabet.chars().collect::<Vec<char>>(), repeats);
+            let numbers = rules.generate_numbers();
+            for candidates_only in [false, true] {
+                let tree = OptimalSearch::new(numbers.clone(), &rules, candidates_only).unwrap().solve();
+                assert_eq!(tree.total_guesses, brute_force_total(&numbers, &numbers, candidates_only), "{} {} {}", alphabet, ndigits, candidates_only);
+            }
+        }
+    }
+    #[test]
+    fn test_optimal_search_tree() {
+        let rules = Ruleset::new(3, &"012345".chars().collect::<Vec<char>>(), false);
+        let numbers = rules.generate_numbers();
+        let tree = OptimalSearch::new(numbers.clone(), &rules, false).unwrap().solve();
+        assert_eq!(tree.secrets, 120);
+        let total: usize = numbers.iter().map(|secret| tree.get_guesses_for(secret).unwrap().len()).sum();
+        assert_eq!(total as u64, tree.total_guesses);
+        assert!(tree.get_average_guesses() < 5.0);
+        let lines = tree.describe();
+        assert!(lines[0].starts_with(&tree.guess));
+        assert!(lines[1].starts_with("  "));
+
+        let candidates_tree = OptimalSearch::new(numbers, &rules, true).unwrap().solve();
+        assert!(candidates_tree.total_guesses >= tree.total_guesses);
+    }
+    #[test]
+    fn test_optimal_search_three_digits() {
+        // deep enough for the symbols left interchangeable to tell the guesses apart
+        let rules = Ruleset::new(3, &"0123456789".chars().collect::<Vec<char>>(), false);
+        let tree = OptimalSearch::new(rules.generate_numbers(), &rules, false).unwrap().solve();
+        assert_eq!((tree.secrets, tree.total_guesses), (720, 3575));
+        assert_eq!(tree.guess, "012");
+    }
+    #[test]
+    fn test_optimal_search_lower_bound() {
+        let rules = Ruleset::new(2, &['1', '2'], false);
+        let search = OptimalSearch::new(vec![String::from("12"), String::from("21")], &rules, false).unwrap();
+        // 2 digits: 0b 0c, 0b 1c, 0b 2c, 1b 0c and the win
+        assert_eq!(search.max_parts, 4);
+        assert_eq!(search.lower_bound(1), 1);
+        assert_eq!(search.lower_bound(5), 1 + 2 * 4);
+        assert_eq!(search.lower_bound(6), 1 + 2 * 4 + 3);
+        // every secret found a guess later, one part per feedback
+        assert_eq!(search.lower_bound_without_win(4), 4 + 4);
+        assert_eq!(search.lower_bound_without_win(5), 5 + 3 + 3);
+        assert_eq!(search.lower_bound_without_win(9), 9 + 3 * 3 + 5);
+        assert!(OptimalSearch::new(Vec::new(), &rules, false).is_none());
+        assert!(OptimalSearch::new(vec![String::from("123")], &rules, false).is_none());
+    }
+    #[test]
+    fn test_optimal_algo_plays_the_optimal_strategy() {
+        let rules = Ruleset::new(3, &"01234".chars().collect::<Vec<char>>(), false);
+        let numbers = rules.generate_numbers();
+        let optimum = OptimalSearch::new(numbers.clone(), &rules, false).unwrap().solve().total_guesses;
+        let mut total: u64 = 0;
+        for secret in numbers.iter() {
+            let mut oa = OptimalAlgo::new(numbers.clone(), &rules).unwrap();
+            loop {
+                let guess = oa.guess().unwrap();
+                total += 1;
+                let (bulls, cows) = get_bulls_and_cows(&guess, secret).unwrap();
+                if bulls == 3 {
+                    break;
+                }
+                assert_eq!(oa.incorporate_guess_feedback(bulls, cows), Some(true));
+            }
+        }
+        assert_eq!(total, optimum);
+    }
+    #[test]
+    fn test_optimal_algo_undo_last_guess() {
+        let rules = Ruleset::new(3, &"01234".chars().collect::<Vec<char>>(), false);
+        let numbers = rules.generate_numbers();
+        let mut oa = OptimalAlgo::new(numbers.clone(), &rules).unwrap();
+        let first = oa.guess().unwrap();
+        oa.incorporate_guess_feedback(0, 1).unwrap();
+        oa.guess().unwrap();
+        assert!(oa.undo_last_guess());
+        assert!(oa.undo_last_guess());
+        assert_eq!(oa.get_numbers_count(), numbers.len());
+        assert_eq!(oa.guess(), Some(first));
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::{HashMap, HashSet};
+use crate::algos::{algos_utils, symmetry};
+use crate::algos::budget::{Budget, BudgetTracker};
+use crate::algos::feedback::FeedbackKind;
+use crate::algos::symmetry::Symmetries;
+use crate::ruleset::Ruleset;
+
+// the search needs the feedback table
+pub const MAX_SEPARATING_NUMBERS: usize = algos_utils::MAX_TABLE_NUMBERS;
+
+// Static Mastermind: guesses fixed in advance, as in a mail-in puzzle where all of them are
+// submitted at once, and the feedbacks they get together must tell which number is the secret.
+// The numbers are grouped by the feedbacks they give to the guesses so far, a separating set
+// leaves every number alone in its group. The smallest one is searched by iterative deepening
+// over sets of guesses, starting from a greedy set and only trying one guess of every class of
+// equivalent ones (see `symmetry`). The same groups are reached by adding the same guesses in
+// another order, groups that cannot be separated in time are remembered.
+pub struct SeparatingSetSearch {
+    numbers : Vec<String>,
+    // set when the numbers are every number over an alphabet, the game is then symmetric
+    universe_alphabet : Option<Vec<char>>,
+    ndigits : usize,
+    // see `algos_utils::build_feedback_table`
+    feedbacks : Vec<u8>,
+    // distinct feedbacks of the kind, the win included: the most groups one guess splits a group into
+    max_parts : usize,
+    feedback_kind : FeedbackKind,
+    // feedback indices of the kind
+    cells : usize,
+    budget : Option<BudgetTracker>,
+    // the most guesses that were not enough to separate the groups
+    failures : HashMap<Vec<u32>, usize>,
+}
+
+// the guesses found, with the fewest guesses a separating set can have as far as the search got
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeparatingSet {
+    pub guesses : Vec<String>,
+    pub lower_bound : usize,
+}
+
+impl SeparatingSet {
+    pub fn is_smallest(&self) -> bool {
+        return self.guesses.len() == self.lower_bound;
+    }
+}
+
+// the group of every number, by index, with the number of groups;
+// groups are numbered in order of their first number so that the same groups look the same
+#[derive(Clone)]
+struct Grouping {
+    groups : Vec<u32>,
+    count : usize,
+    largest : usize,
+    // pairs of numbers in the same group, the ones still to be told apart
+    pairs : u64,
+}
+
+enum Outcome {
+    Found(Vec<u32>),
+    NotFound,
+    OutOfBudget,
+}
+
+impl SeparatingSetSearch {
+    // the numbers are secrets of the rules, their length and feedback kind are the ones of the rules
+    pub fn new(numbers: Vec<String>, rules: &Ruleset) -> Option<SeparatingSetSearch> {
+        if !algos_utils::fit_rules(&numbers, rules) || numbers.len() > MAX_SEPARATING_NUMBERS {
+            return None;
+        }
+        let ndigits: usize = rules.length;
+        let feedback_kind: FeedbackKind = rules.feedback_kind;
+        let feedbacks: Vec<u8> = algos_utils::build_feedback_table(&numbers, feedback_kind)?;
+        let universe_alphabet: Option<Vec<char>> = symmetry::get_universe_alphabet(&numbers, rules);
+        let max_parts: usize = feedback_kind.get_feedback_count(ndigits);
+        return Some(SeparatingSetSearch {numbers, universe_alphabet, ndigits, feedbacks, max_parts, feedback_kind, cells: feedback_kind.get_index_count(ndigits), budget: None, failures: HashMap::new()});
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        return &self.numbers;
+    }
+
+    // the smallest separating set, or the smallest one found when the budget runs out
+    pub fn solve(&mut self, budget: Option<Budget>) -> SeparatingSet {
+        self.budget = budget.map(BudgetTracker::new);
+        self.failures.clear();
+        let mut best: Vec<u32> = self.find_greedy();
+        let mut lower_bound: usize = self.get_lower_bound();
+        while lower_bound < best.len() {
+            match self.search(&self.group_all(), &[], lower_bound) {
+                Outcome::Found(guesses) => {
+                    best = guesses;
+                    break;
+                }
+                Outcome::NotFound => lower_bound += 1,
+                Outcome::OutOfBudget => break,
+            }
+        }
+        let guesses: Vec<String> = best.iter().map(|&guess| self.numbers[guess as usize].clone()).collect();
+        return SeparatingSet {guesses, lower_bound};
+    }
+
+    // adds the guess telling the most pairs of numbers apart until none is left
+    pub fn find_greedy(&self) -> Vec<u32> {
+        let mut grouping: Grouping = self.group_all();
+        let mut guesses: Vec<u32> = Vec::new();
+        while grouping.pairs > 0 {
+            let mut best: Option<(u32, Grouping)> = None;
+            for guess in self.get_guesses(&guesses) {
+                let refined: Grouping = self.refine(&grouping, guess);
+                if best.as_ref().is_none_or(|(_, best)| refined.pairs < best.pairs) {
+                    best = Some((guess, refined));
+                }
+            }
+            // guessing one of two numbers of a group tells them apart, there is always progress
+            let (guess, refined) = best.unwrap();
+            guesses.push(guess);
+            grouping = refined;
+        }
+        return guesses;
+    }
+
+    // whether the feedbacks to the guesses tell every number apart
+    pub fn is_separating<S: AsRef<str>>(&self, guesses: &[S]) -> bool {
+        let mut grouping: Grouping = self.group_all();
+        for guess in guesses.iter() {
+            match self.numbers.iter().position(|number| number == guess.as_ref()) {
+                Some(guess) => grouping = self.refine(&grouping, guess as u32),
+                None => return false,
+            }
+        }
+        return grouping.pairs == 0;
+    }
+
+    // the numbers cannot be told apart with fewer guesses than this
+    fn get_lower_bound(&self) -> usize {
+        let mut guesses: usize = 0;
+        let mut groups: usize = 1;
+        while groups < self.numbers.len() {
+            guesses += 1;
+            groups = groups.saturating_mul(self.max_parts);
+        }
+        return guesses;
+    }
+
+    fn group_all(&self) -> Grouping {
+        let count: u64 = self.numbers.len() as u64;
+        return Grouping {groups: vec![0; self.numbers.len()], count: 1, largest: self.numbers.len(), pairs: count * (count - 1) / 2};
+    }
+
+    // the groups split by the feedback to one more guess
+    fn refine(&self, grouping: &Grouping, guess: u32) -> Grouping {
+        let cells: usize = self.cells;
+        let row: &[u8] = &self.feedbacks[guess as usize * self.numbers.len()..(guess as usize + 1) * self.numbers.len()];
+        let mut ids: Vec<u32> = vec![u32::MAX; grouping.count * cells];
+        let mut sizes: Vec<usize> = Vec::new();
+        let mut groups: Vec<u32> = Vec::with_capacity(self.numbers.len());
+        for (secret, &group) in grouping.groups.iter().enumerate() {
+            let key: usize = group as usize * cells + row[secret] as usize;
+            if ids[key] == u32::MAX {
+                ids[key] = sizes.len() as u32;
+                sizes.push(0);
+            }
+            sizes[ids[key] as usize] += 1;
+            groups.push(ids[key]);
+        }
+        let largest: usize = sizes.iter().copied().max().unwrap_or(0);
+        let pairs: u64 = sizes.iter().map(|&size| (size as u64) * (size as u64 - 1) / 2).sum();
+        return Grouping {groups, count: sizes.len(), largest, pairs};
+    }
+
+    // one guess of every class of equivalent ones given the guesses already chosen
+    fn get_guesses(&self, chosen: &[u32]) -> Vec<u32> {
+        let symmetries: Symmetries = match &self.universe_alphabet {
+            Some(alphabet) => {
+                let past_guesses: Vec<&str> = chosen.iter().map(|&guess| self.numbers[guess as usize].as_str()).collect();
+                Symmetries::new_for_kind(alphabet, self.ndigits, &past_guesses, self.feedback_kind)
+            }
+            None => Symmetries::none(),
+        };
+        let mut seen: HashSet<String> = HashSet::new();
+        return (0..self.numbers.len() as u32)
+            .filter(|&guess| !chosen.contains(&guess) && seen.insert(symmetries.canonical_guess(&self.numbers[guess as usize])))
+            .collect();
+    }
+
+    fn groups_fit(&self, largest: usize, guesses_left: usize) -> bool {
+        let mut groups: usize = 1;
+        for _ in 0..guesses_left {
+            groups = groups.saturating_mul(self.max_parts);
+        }
+        return largest <= groups;
+    }
+
+    // a separating set adding at most `guesses_left` guesses to `chosen`
+    fn search(&mut self, grouping: &Grouping, chosen: &[u32], guesses_left: usize) -> Outcome {
+        if grouping.pairs == 0 {
+            return Outcome::Found(chosen.to_vec());
+        }
+        if guesses_left == 0 || !self.groups_fit(grouping.largest, guesses_left) {
+            return Outcome::NotFound;
+        }
+        if self.failures.get(&grouping.groups).is_some_and(|&failed| failed >= guesses_left) {
+            return Outcome::NotFound;
+        }
+
+        let mut options: Vec<(u64, u32, Grouping)> = Vec::new();
+        for guess in self.get_guesses(chosen) {
+            if let Some(budget) = self.budget.as_mut() {
+                if budget.is_exhausted() {
+                    return Outcome::OutOfBudget;
+                }
+                budget.record_iteration();
+            }
+            let refined: Grouping = self.refine(grouping, guess);
+            // a guess that splits no group never helps
+            if refined.count == grouping.count || !self.groups_fit(refined.largest, guesses_left - 1) {
+                continue;
+            }
+            options.push((refined.pairs, guess, refined));
+        }
+        options.sort_by_key(|(pairs, guess, _)| (*pairs, *guess));
+
+        for (_, guess, refined) in options {
+            let next_chosen: Vec<u32> = [chosen, &[guess]].concat();
+            match self.search(&refined, &next_chosen, guesses_left - 1) {
+                Outcome::NotFound => continue,
+                outcome => return outcome,
+            }
+        }
+        self.failures.insert(grouping.groups.clone(), guesses_left);
+        return Outcome::NotFound;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::budget::Budget;
+    use crate::algos::feedback::FeedbackKind;
+    use crate::ruleset::Ruleset;
+    use super::SeparatingSetSearch;
+
+    fn rules_of(alphabet: &str, ndigits: usize, repeats: bool) -> Ruleset {
+        return Ruleset::new(ndigits, &alphabet.chars().collect::<Vec<char>>(), repeats);
+    }
+
+    // the size of the smallest separating set, trying every set of guesses
+    fn brute_force_size(search: &SeparatingSetSearch) -> usize {
+        let count: usize = search.get_numbers().len();
+        for size in 1.. {
+            let mut indices: Vec<usize> = (0..size).collect();
+            loop {
+                let guesses: Vec<&String> = indices.iter().map(|&i| &search.get_numbers()[i]).collect();
+                if search.is_separating(&guesses) {
+                    return size;
+                }
+                // next combination
+                let mut i: usize = size;
+                while i > 0 && indices[i - 1] == count - size + i - 1 {
+                    i -= 1;
+                }
+                if i == 0 {
+                    break;
+                }
+                indices[i - 1] += 1;
+                for j in i..size {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+        }
+        unreachable!();
+    }
+
+    #[test]
+    fn test_separating_set_matches_brute_force() {
+        for (alphabet, ndigits, repeats) in [("0123", 2, false), ("01234", 2, false), ("012", 2, true), ("0123", 3, false)] {
+            let rules = rules_of(alphabet, ndigits, repeats);
+            let mut search = SeparatingSetSearch::new(rules.generate_numbers(), &rules).unwrap();
+            let set = search.solve(None);
+            assert!(set.is_smallest());
+            assert!(search.is_separating(&set.guesses));
+            assert_eq!(set.guesses.len(), brute_force_size(&search), "{} {}", alphabet, ndigits);
+        }
+    }
+    #[test]
+    fn test_separating_set_feedback_kinds_and_budget() {
+        let rules = rules_of("012345", 3, false);
+        let numbers = rules.generate_numbers();
+        let mut search = SeparatingSetSearch::new(numbers.clone(), &rules).unwrap();
+        let greedy: Vec<String> = search.find_greedy().iter().map(|&guess| numbers[guess as usize].clone()).collect();
+        assert!(search.is_separating(&greedy));
+        let set = search.solve(None);
+        assert!(set.is_smallest() && set.guesses.len() <= greedy.len());
+        assert!(!search.is_separating(&set.guesses[1..]));
+        assert!(!search.is_separating(&["999"]));
+
+        // no search at all leaves the greedy set
+        let unsearched = search.solve(Some(Budget::Iterations(0)));
+        assert!(search.is_separating(&unsearched.guesses));
+        assert!(unsearched.lower_bound <= set.lower_bound);
+
+        // less feedback needs more guesses
+        let rules = rules_of("01234", 3, false);
+        let numbers = rules.generate_numbers();
+        let set = SeparatingSetSearch::new(numbers.clone(), &rules).unwrap().solve(None);
+        let bulls = SeparatingSetSearch::new(numbers, &Ruleset { feedback_kind: FeedbackKind::BullsOnly, ..rules.clone() }).unwrap().solve(None);
+        assert!(set.is_smallest() && bulls.is_smallest() && bulls.guesses.len() > set.guesses.len());
+        assert!(SeparatingSetSearch::new(Vec::new(), &rules).is_none());
+        assert!(SeparatingSetSearch::new(vec![String::from("0123")], &rules).is_none());
+    }
+}
@@ -0,0 +1,278 @@
+use crate::algos::feedback::FeedbackKind;
+use crate::ruleset::Ruleset;
+
+// Guesses that are equivalent under a symmetry of the game so far split the remaining
+// numbers the same way, so only one guess per class needs to be scored.
+// A symmetry permutes the positions and relabels the symbols while leaving every past
+// guess unchanged: it then maps the numbers consistent with the feedback onto themselves.
+// Symbols that no guess has used yet can always be relabeled among themselves.
+
+// beyond this the permutations of the positions are not tried, only the unused symbols are relabeled
+pub const MAX_PERMUTED_POSITIONS: usize = 6;
+
+// a position permutation with the relabeling of the used symbols that goes with it,
+// a number x becomes relabeling(x[positions[0]]) relabeling(x[positions[1]]) ...
+type Relabeling = (Vec<usize>, Vec<(char, char)>);
+
+pub struct Symmetries {
+    alphabet : Vec<char>,
+    used_symbols : Vec<char>,
+    relabelings : Vec<Relabeling>,
+}
+
+// the alphabet of the rules if the numbers, secrets of the rules, are all of them and no symbol
+// is set apart by the rules (no word list, no forbidden leading symbol), only then is the game symmetric
+pub fn get_universe_alphabet(numbers: &[String], rules: &Ruleset) -> Option<Vec<char>> {
+    if rules.words.is_some() || !rules.no_leading.is_empty() || numbers.len() as u64 != rules.get_numbers_count() {
+        return None;
+    }
+    return Some(rules.alphabet.clone());
+}
+
+fn get_permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut permutations: Vec<Vec<usize>> = Vec::new();
+    for permutation in get_permutations(n - 1) {
+        for i in 0..n {
+            let mut extended: Vec<usize> = permutation.clone();
+            extended.insert(i, n - 1);
+            permutations.push(extended);
+        }
+    }
+    return permutations;
+}
+
+impl Symmetries {
+    // every guess is in its own class
+    pub fn none() -> Symmetries {
+        return Symmetries {alphabet: Vec::new(), used_symbols: Vec::new(), relabelings: Vec::new()};
+    }
+
+    pub fn new<S: AsRef<str>>(alphabet: &[char], ndigits: usize, past_guesses: &[S]) -> Symmetries {
+        let past_guesses: Vec<Vec<char>> = past_guesses.iter().map(|guess| guess.as_ref().chars().collect()).collect();
+        let mut used_symbols: Vec<char> = past_guesses.iter().flatten().copied().collect();
+        used_symbols.sort();
+        used_symbols.dedup();
+
+        let permutations: Vec<Vec<usize>> = if ndigits <= MAX_PERMUTED_POSITIONS { get_permutations(ndigits) } else { vec![(0..ndigits).collect()] };
+        let mut relabelings: Vec<Relabeling> = Vec::new();
+        for positions in permutations {
+            if let Some(relabeling) = Symmetries::find_relabeling(&positions, &past_guesses) {
+                relabelings.push((positions, relabeling));
+            }
+        }
+        return Symmetries {alphabet: alphabet.to_vec(), used_symbols, relabelings};
+    }
+
+    // the symmetries that keep the feedback of the kind the same: permuting the positions would move
+    // the positional marks, only the unused symbols are relabeled then
+    pub fn new_for_kind<S: AsRef<str>>(alphabet: &[char], ndigits: usize, past_guesses: &[S], feedback_kind: FeedbackKind) -> Symmetries {
+        let mut symmetries: Symmetries = Symmetries::new(alphabet, ndigits, past_guesses);
+        if feedback_kind == FeedbackKind::Positional {
+            symmetries.relabelings.retain(|(positions, _)| positions.iter().enumerate().all(|(i, &position)| i == position));
+        }
+        return symmetries;
+    }
+
+    // the only relabeling that maps every past guess onto itself once its positions are permuted, if any
+    fn find_relabeling(positions: &[usize], past_guesses: &[Vec<char>]) -> Option<Vec<(char, char)>> {
+        let mut relabeling: Vec<(char, char)> = Vec::new();
+        for guess in past_guesses.iter() {
+            if guess.len() != positions.len() {
+                return None;
+            }
+            for (i, &position) in positions.iter().enumerate() {
+                let (from, to) = (guess[position], guess[i]);
+                match relabeling.iter().find(|(other_from, other_to)| *other_from == from || *other_to == to) {
+                    Some(&pair) if pair != (from, to) => return None,
+                    Some(_) => {}
+                    None => relabeling.push((from, to)),
+                }
+            }
+        }
+        return Some(relabeling);
+    }
+
+    // the same for all the guesses of a class: the smallest image of the guess under the symmetries,
+    // with its unused symbols renamed to the first unused symbols of the alphabet in order of appearance
+    pub fn canonical_guess(&self, guess: &str) -> String {
+        let symbols: Vec<char> = guess.chars().collect();
+        let mut canonical: Option<String> = None;
+        for (positions, relabeling) in self.relabelings.iter() {
+            if positions.len() != symbols.len() {
+                continue;
+            }
+            let image: String = self.rename_unused_symbols(positions.iter().map(|&position| {
+                let symbol = symbols[position];
+                relabeling.iter().find(|(from, _)| *from == symbol).map_or(symbol, |(_, to)| *to)
+            }));
+            if canonical.as_ref().is_none_or(|canonical| image < *canonical) {
+                canonical = Some(image);
+            }
+        }
+        return canonical.unwrap_or_else(|| String::from(guess));
+    }
+
+    fn rename_unused_symbols(&self, symbols: impl Iterator<Item = char>) -> String {
+        let mut unused = self.alphabet.iter().filter(|symbol| !self.used_symbols.contains(symbol));
+        let mut renamed: Vec<(char, char)> = Vec::new();
+        let mut result = String::new();
+        for symbol in symbols {
+            if self.used_symbols.contains(&symbol) {
+                result.push(symbol);
+                continue;
+            }
+            match renamed.iter().find(|(from, _)| *from == symbol) {
+                Some((_, to)) => result.push(*to),
+                None => {
+                    // symbols outside the alphabet are kept
+                    let to = unused.next().copied().unwrap_or(symbol);
+                    renamed.push((symbol, to));
+                    result.push(to);
+                }
+            }
+        }
+        return result;
+    }
+
+    // the first guess of every class, in the order of the guesses
+    pub fn get_representatives<'a, S: AsRef<str>>(&self, guesses: &'a [S]) -> Vec<&'a S> {
+        if self.relabelings.is_empty() {
+            return guesses.iter().collect();
+        }
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        return guesses.iter().filter(|guess| seen.insert(self.canonical_guess(guess.as_ref()))).collect();
+    }
+}
+
+// Symbols that can be swapped two at a time without changing a set of numbers: any relabeling
+// among them maps the set onto itself, so guesses that differ by such a relabeling split it alike.
+// Unlike `Symmetries` they follow from the numbers left and not from the guesses made.
+pub struct SymbolClasses {
+    // the class of every symbol of the alphabet, in the order of the alphabet
+    classes : Vec<Vec<char>>,
+}
+
+impl SymbolClasses {
+    pub fn new<S: AsRef<str>>(alphabet: &[char], numbers: &[S]) -> SymbolClasses {
+        let set: std::collections::HashSet<&str> = numbers.iter().map(|number| number.as_ref()).collect();
+        let mut classes: Vec<Vec<char>> = Vec::new();
+        for &symbol in alphabet.iter() {
+            // swaps within a class are symmetries, so are the swaps with any symbol of a class
+            let class = classes.iter_mut().find(|class| numbers.iter().all(|number| {
+                let swapped: String = number.as_ref().chars().map(|c| if c == class[0] { symbol } else if c == symbol { class[0] } else { c }).collect();
+                return set.contains(swapped.as_str());
+            }));
+            match class {
+                Some(class) => class.push(symbol),
+                None => classes.push(vec![symbol]),
+            }
+        }
+        return SymbolClasses {classes};
+    }
+
+    // the same for all the guesses of a class: the symbols of every class renamed to the symbols
+    // of the class in order of appearance, symbols outside the alphabet are kept
+    pub fn canonical_guess(&self, guess: &str) -> String {
+        let mut renamed: Vec<(char, char)> = Vec::new();
+        let mut result = String::new();
+        for symbol in guess.chars() {
+            if let Some((_, to)) = renamed.iter().find(|(from, _)| *from == symbol) {
+                result.push(*to);
+                continue;
+            }
+            let to: char = match self.classes.iter().find(|class| class.contains(&symbol)) {
+                Some(class) => class[renamed.iter().filter(|(_, to)| class.contains(to)).count()],
+                None => symbol,
+            };
+            renamed.push((symbol, to));
+            result.push(to);
+        }
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_permutations, get_universe_alphabet, SymbolClasses, Symmetries};
+    use crate::ruleset::Ruleset;
+    use crate::algos::algos_utils::{generate_numbers, get_bulls_and_cows, get_partition_sizes};
+
+    fn digits() -> Vec<char> {
+        return "0123456789".chars().collect();
+    }
+
+    #[test]
+    fn test_get_universe_alphabet() {
+        let rules = Ruleset::new(3, &digits(), false);
+        let numbers = generate_numbers(&digits(), 3, false);
+        assert_eq!(get_universe_alphabet(&numbers, &rules), Some(digits()));
+        let rules = Ruleset::new(2, &['a', 'b', 'c'], true);
+        let numbers = generate_numbers(&['a', 'b', 'c'], 2, true);
+        assert_eq!(get_universe_alphabet(&numbers, &rules), Some(vec!['a', 'b', 'c']));
+        assert_eq!(get_universe_alphabet(&[String::from("1234"), String::from("5678")], &Ruleset::classic()), None);
+        assert_eq!(get_universe_alphabet(&[], &Ruleset::classic()), None);
+        // a leading zero is not allowed, the zero is not like the other digits
+        let rules = Ruleset { no_leading: vec!['0'], ..Ruleset::new(3, &digits(), false) };
+        assert_eq!(get_universe_alphabet(&rules.generate_numbers(), &rules), None);
+    }
+    #[test]
+    fn test_get_permutations() {
+        assert_eq!(get_permutations(3).len(), 6);
+        assert!(get_permutations(3).contains(&vec![2, 0, 1]));
+    }
+    #[test]
+    fn test_canonical_guess_unused_symbols() {
+        let symmetries = Symmetries::new(&digits(), 4, &[] as &[&str]);
+        assert_eq!(symmetries.canonical_guess("5678"), "0123");
+        assert_eq!(symmetries.canonical_guess("9876"), "0123");
+        let symmetries = Symmetries::new(&digits(), 4, &["0011"]);
+        // 0 and 1 were guessed, 2 is the first unused symbol
+        assert_eq!(symmetries.canonical_guess("1907"), symmetries.canonical_guess("1502"));
+        assert_eq!(Symmetries::none().canonical_guess("1907"), "1907");
+    }
+    #[test]
+    fn test_canonical_guess_positions() {
+        // swapping the first two positions and relabeling 0 and 1 leaves 0123 unchanged
+        let symmetries = Symmetries::new(&digits(), 4, &["0123"]);
+        assert_eq!(symmetries.canonical_guess("0456"), symmetries.canonical_guess("4156"));
+        assert_eq!(symmetries.canonical_guess("3210"), symmetries.canonical_guess("1032"));
+        assert_ne!(symmetries.canonical_guess("0123"), symmetries.canonical_guess("1023"));
+    }
+    #[test]
+    fn test_representatives_split_the_numbers_alike() {
+        let numbers = generate_numbers(&digits(), 4, false);
+        assert_eq!(Symmetries::new(&digits(), 4, &[] as &[&str]).get_representatives(&numbers).len(), 1);
+
+        let history = ["0123", "0456"];
+        let consistent: Vec<String> = numbers.iter().filter(|number| get_bulls_and_cows("0123", number) == Some((1, 1)) && get_bulls_and_cows("0456", number) == Some((0, 1))).cloned().collect();
+        let symmetries = Symmetries::new(&digits(), 4, &history);
+        let representatives = symmetries.get_representatives(&numbers);
+        assert!(representatives.len() < numbers.len() / 10);
+        // guesses of the same class split the consistent numbers the same way
+        for guess in numbers.iter().step_by(37) {
+            let representative = representatives.iter().find(|representative| symmetries.canonical_guess(representative) == symmetries.canonical_guess(guess)).unwrap();
+            assert_eq!(get_partition_sizes(guess, &consistent, 4), get_partition_sizes(representative, &consistent, 4), "{} {}", guess, representative);
+        }
+    }
+    #[test]
+    fn test_symbol_classes() {
+        let numbers = generate_numbers(&digits(), 4, false);
+        // no bull and no cow against 0123, 4 to 9 are left in any order
+        let consistent: Vec<String> = numbers.iter().filter(|number| get_bulls_and_cows("0123", number) == Some((0, 0))).cloned().collect();
+        let classes = SymbolClasses::new(&digits(), &consistent);
+        assert_eq!(classes.canonical_guess("9876"), "4567");
+        assert_eq!(classes.canonical_guess("0819"), "0415");
+        assert_eq!(classes.canonical_guess("0819"), classes.canonical_guess("1904"));
+        assert_ne!(classes.canonical_guess("0123"), classes.canonical_guess("0145"));
+        for guess in ["0819", "1904", "0145", "4015"] {
+            assert_eq!(get_partition_sizes(guess, &consistent, 4), get_partition_sizes(&classes.canonical_guess(guess), &consistent, 4), "{}", guess);
+        }
+        // nothing can be swapped in a single number but the symbols it does not use
+        let classes = SymbolClasses::new(&digits(), &["0123"]);
+        assert_eq!(classes.canonical_guess("0123"), "0123");
+        assert_eq!(classes.canonical_guess("1098"), "1045");
+    }
+}
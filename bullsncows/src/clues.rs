@@ -0,0 +1,113 @@
+// Pico-Fermi-Bagels tells the feedback with words: "Fermi" for every right digit in the
+// right place, "Pico" for every right digit in the wrong place and "Bagels" when no digit
+// is right. The clues are sorted, Fermis first, so they tell no more than the counts:
+// it is bulls and cows written differently and the solvers play it unchanged.
+
+pub const FERMI: &str = "Fermi";
+pub const PICO: &str = "Pico";
+pub const BAGELS: &str = "Bagels";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedbackStyle {
+    // "1b 2c"
+    #[default]
+    BullsAndCows,
+    // "Fermi Pico Pico"
+    PicoFermiBagels,
+}
+
+impl FeedbackStyle {
+    pub fn format_feedback(&self, feedback: (usize, usize)) -> String {
+        match self {
+            FeedbackStyle::BullsAndCows => return format!("{}b {}c", feedback.0, feedback.1),
+            FeedbackStyle::PicoFermiBagels => return format_clues(feedback),
+        }
+    }
+
+    // a line of a transcript, `transcript::parse_line` reads both styles
+    pub fn format_line(&self, guess: &str, feedback: (usize, usize)) -> String {
+        return format!("{} {}", guess, self.format_feedback(feedback));
+    }
+}
+
+pub fn format_clues(feedback: (usize, usize)) -> String {
+    let (fermis, picos) = feedback;
+    if fermis + picos == 0 {
+        return String::from(BAGELS);
+    }
+    let mut clues: Vec<&str> = vec![FERMI; fermis];
+    clues.extend(vec![PICO; picos]);
+    return clues.join(" ");
+}
+
+// the words in any order and case, None for anything else or "Bagels" with other clues
+pub fn parse_clues(text: &str) -> Option<(usize, usize)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() == 1 && words[0].eq_ignore_ascii_case(BAGELS) {
+        return Some((0, 0));
+    }
+    if words.is_empty() {
+        return None;
+    }
+    let mut feedback: (usize, usize) = (0, 0);
+    for word in words {
+        if word.eq_ignore_ascii_case(FERMI) {
+            feedback.0 += 1;
+        } else if word.eq_ignore_ascii_case(PICO) {
+            feedback.1 += 1;
+        } else {
+            return None;
+        }
+    }
+    return Some(feedback);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::algos::algos_utils::{generate_default_init_values_for_numbers, generate_possible_feedbacks, get_bulls_and_cows, get_partition_sizes};
+    use super::{format_clues, parse_clues, FeedbackStyle};
+
+    #[test]
+    fn test_format_and_parse_clues() {
+        assert_eq!(format_clues((0, 0)), "Bagels");
+        assert_eq!(format_clues((2, 1)), "Fermi Fermi Pico");
+        assert_eq!(parse_clues("pico FERMI pico"), Some((1, 2)));
+        assert_eq!(parse_clues("bagels"), Some((0, 0)));
+        assert_eq!(parse_clues("Bagels Pico"), None);
+        assert_eq!(parse_clues("Fermi Moo"), None);
+        assert_eq!(parse_clues(""), None);
+        for feedback in generate_possible_feedbacks(4) {
+            assert_eq!(parse_clues(&format_clues(feedback)), Some(feedback));
+        }
+        assert_eq!(FeedbackStyle::default().format_line("1234", (1, 2)), "1234 1b 2c");
+        assert_eq!(FeedbackStyle::PicoFermiBagels.format_line("1234", (0, 0)), "1234 Bagels");
+    }
+    #[test]
+    fn test_clues_partition_like_bulls_and_cows() {
+        // grouping the numbers by the clues they give splits them exactly like bulls and cows,
+        // so the partition sizes every heuristic scores are the same
+        let numbers = generate_default_init_values_for_numbers();
+        for guess in numbers.iter().step_by(97) {
+            let mut by_clues: HashMap<String, Vec<&String>> = HashMap::new();
+            let mut by_feedback: HashMap<(usize, usize), Vec<&String>> = HashMap::new();
+            for number in numbers.iter() {
+                let feedback = get_bulls_and_cows(guess, number).unwrap();
+                by_clues.entry(format_clues(feedback)).or_default().push(number);
+                by_feedback.entry(feedback).or_default().push(number);
+            }
+            let mut clue_parts: Vec<Vec<&String>> = by_clues.into_values().collect();
+            let mut feedback_parts: Vec<Vec<&String>> = by_feedback.into_values().collect();
+            clue_parts.sort();
+            feedback_parts.sort();
+            assert_eq!(clue_parts, feedback_parts);
+
+            let partition_sizes = get_partition_sizes(guess, &numbers, 4);
+            let mut clue_sizes: Vec<usize> = clue_parts.iter().map(|part| part.len()).collect();
+            let mut sizes: Vec<usize> = partition_sizes.iter().copied().filter(|&size| size > 0).collect();
+            clue_sizes.sort();
+            sizes.sort();
+            assert_eq!(clue_sizes, sizes);
+        }
+    }
+}
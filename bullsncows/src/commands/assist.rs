@@ -0,0 +1,122 @@
+use std::io::{self, BufRead};
+use bullsncows::algos::feedback::FeedbackKind;
+use bullsncows::algos::knowledge::Knowledge;
+use crate::commands::{exit_with_error, SolverArgs, VariantArgs};
+
+enum Correction {
+    Undo,
+    // guess number from 1, corrected bulls and cows
+    Fix(usize, (usize, usize)),
+}
+
+// "undo" or "fix 2 1 2" / "fix 2 1b 2c", the feedback as read by the kind
+fn parse_correction(line: &str, feedback_kind: FeedbackKind, length: usize) -> Option<Correction> {
+    let line: &str = line.trim();
+    if line == "undo" {
+        return Some(Correction::Undo);
+    }
+    let (index, feedback) = line.strip_prefix("fix ")?.trim_start().split_once(char::is_whitespace)?;
+    return Some(Correction::Fix(index.parse().ok()?, feedback_kind.parse(feedback, length)?));
+}
+
+fn get_prompt(variant: &VariantArgs) -> &'static str {
+    if variant.pico_fermi_bagels {
+        return "Clues? (e.g. \"Fermi Pico Pico\" or \"Bagels\", \"undo\" or \"fix 1 Fermi Pico\" to correct guess 1)";
+    }
+    match variant.get_feedback_kind() {
+        FeedbackKind::BullsAndCows => return "Bulls and cows? (e.g. \"1 2\" or \"1b 2c\", \"undo\" or \"fix 1 2 1\" to correct guess 1)",
+        FeedbackKind::BullsOnly => return "Bulls? (e.g. \"2\" or \"win\", \"undo\" or \"fix 1 2\" to correct guess 1)",
+        FeedbackKind::Total => return "Bulls and cows together? (e.g. \"3\" or \"win\", \"undo\" or \"fix 1 3\" to correct guess 1)",
+        FeedbackKind::AnyBull => return "Any bull? (\"yes\", \"no\" or \"win\", \"undo\" or \"fix 1 no\" to correct guess 1)",
+        FeedbackKind::Positional => return "Marks? (B for a bull, C for a cow, - otherwise, e.g. \"BC--\" or \"win\", \"undo\" or \"fix 1 B-C-\" to correct guess 1)",
+    }
+}
+
+// the user plays against someone else's secret and types in the feedback they get,
+// wrong feedback can be taken back with "undo" or corrected with "fix"
+pub fn run(variant: &VariantArgs, solver_args: &SolverArgs) {
+    variant.check_solvable().unwrap_or_else(|message| exit_with_error(&message));
+    let feedback_kind: FeedbackKind = variant.get_feedback_kind();
+    let mut solver = solver_args.create(variant.get_ruleset(), variant.seed).unwrap_or_else(|message| exit_with_error(&message));
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    // the guesses with feedback so far, the user numbers them from 1
+    let mut answered: usize = 0;
+
+    loop {
+        let knowledge = Knowledge::from_solver(solver.as_ref(), variant.get_ruleset());
+        let guess: Option<String> = solver.guess();
+        match &guess {
+            Some(guess) => {
+                println!("Try {} ({} possible numbers left)", guess, solver.get_numbers_count() + 1);
+                if let Some(knowledge) = knowledge {
+                    println!("  {}", knowledge.explain_guess(guess));
+                }
+            }
+            None if answered == 0 => {
+                println!("No number is consistent with the feedback given so far");
+                return;
+            }
+            None => println!("No number is consistent with the feedback given so far, \"undo\" or \"fix\" it"),
+        }
+
+        loop {
+            println!("{}", get_prompt(variant));
+            let line = match lines.next() {
+                Some(line) => line.expect("Error while reading user input"),
+                None => return,
+            };
+            match parse_correction(&line, feedback_kind, variant.get_length()) {
+                Some(Correction::Undo) if answered > 0 => {
+                    // the pending guess goes too, it was chosen from the wrong feedback
+                    if guess.is_some() {
+                        solver.undo_last_guess();
+                    }
+                    solver.undo_last_guess();
+                    answered -= 1;
+                    break;
+                }
+                Some(Correction::Fix(index, (bulls, cows))) if (1..=answered).contains(&index) => {
+                    if solver.revise_feedback(index - 1, bulls, cows) == Some(true) {
+                        if guess.is_some() {
+                            solver.undo_last_guess();
+                        }
+                        break;
+                    }
+                    println!("Invalid input!");
+                    continue;
+                }
+                Some(_) => {
+                    println!("Invalid input!");
+                    continue;
+                }
+                None => {}
+            }
+            let guess: &str = match &guess {
+                Some(guess) => guess,
+                None => {
+                    println!("Invalid input!");
+                    continue;
+                }
+            };
+            let (bulls, cows) = match feedback_kind.parse(&line, variant.get_length()) {
+                Some(feedback) => feedback,
+                None => {
+                    println!("Invalid input!");
+                    continue;
+                }
+            };
+            if bulls == variant.get_length() && cows == 0 {
+                println!("Solved: {}", guess);
+                return;
+            }
+            match solver.incorporate_guess_feedback(bulls, cows) {
+                Some(true) => {
+                    answered += 1;
+                    break;
+                }
+                _ => println!("Invalid input!"),
+            }
+        }
+    }
+}
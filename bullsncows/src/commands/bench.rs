@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+use rand::seq::SliceRandom;
+use serde_json::json;
+use bullsncows::game::GameState;
+use crate::commands::{exit_with_error, solve, OutputFormat, SolverArgs, SolverListArgs, VariantArgs};
+
+struct BenchResult {
+    solver: String,
+    guess_counts: BTreeMap<u64, usize>,
+    unsolved: usize,
+    seconds: f64,
+}
+
+impl BenchResult {
+    fn get_solved(&self) -> usize {
+        return self.guess_counts.values().sum();
+    }
+
+    fn get_average(&self) -> Option<f64> {
+        let solved = self.get_solved();
+        if solved == 0 {
+            return None;
+        }
+        let total_guesses: u64 = self.guess_counts.iter().map(|(guesses, count)| guesses * *count as u64).sum();
+        return Some(total_guesses as f64 / solved as f64);
+    }
+
+    fn get_worst(&self) -> Option<u64> {
+        return self.guess_counts.keys().next_back().copied();
+    }
+}
+
+// plays every solver against the same `games` random secrets (or every secret with `all`)
+pub fn run(variant: &VariantArgs, solver_list: &SolverListArgs, games: usize, all: bool, format: OutputFormat) {
+    let numbers = variant.generate_numbers().unwrap_or_else(|message| exit_with_error(&message));
+    let mut rng = variant.make_rng();
+    let secrets: Vec<String> = if all {
+        numbers.clone()
+    } else {
+        (0..games).map(|_| numbers.choose(&mut rng).unwrap().clone()).collect()
+    };
+
+    let results: Vec<BenchResult> = solver_list.get_solver_args().iter().map(|solver_args| bench_solver(variant, solver_args, &secrets)).collect();
+    match format {
+        OutputFormat::Text => {
+            for result in results.iter() {
+                print_result(result, secrets.len());
+            }
+            if results.len() > 1 {
+                println!("solver          average  worst");
+                for result in results.iter() {
+                    match result.get_average() {
+                        Some(average) => println!("{:<15} {:>7.4} {:>6}", result.solver, average, result.get_worst().unwrap()),
+                        None => println!("{:<15} {:>7} {:>6}", result.solver, "-", "-"),
+                    }
+                }
+            }
+        }
+        // one object per line and solver
+        OutputFormat::Json => {
+            for result in results.iter() {
+                let distribution: BTreeMap<String, usize> = result.guess_counts.iter().map(|(guesses, count)| (guesses.to_string(), *count)).collect();
+                println!("{}", json!({
+                    "solver": result.solver,
+                    "games": secrets.len(),
+                    "solved": result.get_solved(),
+                    "unsolved": result.unsolved,
+                    "average_guesses": result.get_average(),
+                    "worst_guesses": result.get_worst(),
+                    "distribution": distribution,
+                    "seconds": result.seconds,
+                }));
+            }
+        }
+    }
+}
+
+fn bench_solver(variant: &VariantArgs, solver_args: &SolverArgs, secrets: &[String]) -> BenchResult {
+    let start = Instant::now();
+    let mut guess_counts: BTreeMap<u64, usize> = BTreeMap::new();
+    let mut unsolved: usize = 0;
+    for (i, secret) in secrets.iter().enumerate() {
+        let mut g = variant.new_game_with_answer(secret).unwrap();
+        let mut solver = solver_args.create(variant.get_ruleset(), variant.seed.map(|seed| seed.wrapping_add(i as u64)))
+            .unwrap_or_else(|message| exit_with_error(&message));
+        solve::play_solver_game(solver.as_mut(), &mut g);
+        if g.get_state() == GameState::Won {
+            *guess_counts.entry(g.get_guess_count()).or_insert(0) += 1;
+        } else {
+            unsolved += 1;
+        }
+    }
+    return BenchResult { solver: solver_args.solver.clone(), guess_counts, unsolved, seconds: start.elapsed().as_secs_f64() };
+}
+
+fn print_result(result: &BenchResult, games: usize) {
+    println!("solver: {}", result.solver);
+    println!("games: {}, solved: {}, unsolved: {}", games, result.get_solved(), result.unsolved);
+    if let Some(average) = result.get_average() {
+        println!("average guesses: {:.4}, worst: {}", average, result.get_worst().unwrap());
+    }
+    for (guesses, count) in result.guess_counts.iter() {
+        println!("{:>3} guesses: {}", guesses, count);
+    }
+    println!("time: {:.2}s", result.seconds);
+}
@@ -0,0 +1,73 @@
+use std::io::{self, BufRead, Write};
+use serde_json::json;
+use bullsncows::algos::Solver;
+use bullsncows::algos::feedback::FeedbackKind;
+use crate::commands::{exit_with_error, OutputFormat, SolverArgs, VariantArgs};
+
+// Line based protocol for driving a solver from another program:
+//
+// new              start a new game                   -> ok
+// guess            ask for the next guess             -> guess <number>
+// feedback <b> <c> feedback for the last guess        -> ok <remaining candidates>
+//                  (as the --feedback kind writes it, e.g. "feedback 2" for bulls only or "feedback BC--")
+// count            number of remaining candidates     -> count <n>
+// quit             exit
+//
+// failures are answered with "error <reason>", with --format json every answer is a JSON object
+pub fn run(variant: &VariantArgs, solver_args: &SolverArgs, format: OutputFormat) {
+    variant.check_solvable().unwrap_or_else(|message| exit_with_error(&message));
+    let mut solver = solver_args.create(variant.get_ruleset(), variant.seed).unwrap_or_else(|message| exit_with_error(&message));
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line.expect("Error while reading engine input");
+        let (command, argument) = match line.trim().split_once(' ') {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line.trim(), ""),
+        };
+        let response = match command {
+            "" => continue,
+            "quit" => break,
+            "new" => {
+                solver = solver_args.create(variant.get_ruleset(), variant.seed).unwrap_or_else(|message| exit_with_error(&message));
+                Ok(Response::Ok(None))
+            }
+            "guess" => solver.guess().map(Response::Guess).ok_or("no guess available"),
+            "count" => Ok(Response::Count(solver.get_numbers_count())),
+            "feedback" => incorporate_feedback(solver.as_mut(), variant.get_feedback_kind(), variant.get_length(), argument),
+            _ => Err("unknown command"),
+        };
+        writeln!(stdout, "{}", format_response(response, format)).expect("Error while writing engine output");
+        stdout.flush().expect("Error while writing engine output");
+    }
+}
+
+enum Response {
+    Ok(Option<usize>),
+    Guess(String),
+    Count(usize),
+}
+
+fn incorporate_feedback(solver: &mut dyn Solver, feedback_kind: FeedbackKind, length: usize, argument: &str) -> Result<Response, &'static str> {
+    let (bulls, cows) = feedback_kind.parse(argument, length).ok_or("invalid feedback")?;
+    match solver.incorporate_guess_feedback(bulls, cows) {
+        Some(true) => return Ok(Response::Ok(Some(solver.get_numbers_count()))),
+        Some(false) => return Err("no guess to give feedback for"),
+        None => return Err("invalid feedback"),
+    }
+}
+
+fn format_response(response: Result<Response, &'static str>, format: OutputFormat) -> String {
+    match (response, format) {
+        (Ok(Response::Ok(None)), OutputFormat::Text) => return String::from("ok"),
+        (Ok(Response::Ok(Some(count))), OutputFormat::Text) => return format!("ok {}", count),
+        (Ok(Response::Guess(guess)), OutputFormat::Text) => return format!("guess {}", guess),
+        (Ok(Response::Count(count)), OutputFormat::Text) => return format!("count {}", count),
+        (Err(reason), OutputFormat::Text) => return format!("error {}", reason),
+        (Ok(Response::Ok(count)), OutputFormat::Json) => return json!({"ok": true, "remaining": count}).to_string(),
+        (Ok(Response::Guess(guess)), OutputFormat::Json) => return json!({"ok": true, "guess": guess}).to_string(),
+        (Ok(Response::Count(count)), OutputFormat::Json) => return json!({"ok": true, "remaining": count}).to_string(),
+        (Err(reason), OutputFormat::Json) => return json!({"ok": false, "error": reason}).to_string(),
+    }
+}
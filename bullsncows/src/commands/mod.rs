@@ -0,0 +1,281 @@
+pub mod assist;
+pub mod bench;
+pub mod engine;
+pub mod multi;
+pub mod optimal;
+pub mod play;
+pub mod replay;
+pub mod scores;
+pub mod separating;
+pub mod serve;
+pub mod solve;
+pub mod tui;
+
+use clap::{builder::PossibleValuesParser, Args, ValueEnum};
+use rand::{rngs::StdRng, SeedableRng};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use bullsncows::algos::{self, algos_utils, Solver};
+use bullsncows::algos::budget::Budget;
+use bullsncows::algos::feedback::{FeedbackKind, FEEDBACK_KINDS};
+use bullsncows::algos::optimal_algo::MAX_OPTIMAL_NUMBERS;
+use bullsncows::clues::FeedbackStyle;
+use bullsncows::dictionary;
+use bullsncows::game::Game;
+use bullsncows::mastermind;
+use bullsncows::ruleset::{self, Ruleset};
+
+// solvers enumerate every possible secret, bigger variants are refused
+const MAX_CANDIDATES: u64 = 2_000_000;
+// the options a preset or a ruleset file replaces
+const VARIANT_OPTIONS: [&str; 7] = ["length", "alphabet", "repeats", "colors", "feedback", "words", "max_guesses"];
+
+// the variant is given by the options, a preset or a ruleset file; the options and the preset
+// are read into a ruleset too, see `get_ruleset`
+#[derive(Args, Clone, Debug)]
+pub struct VariantArgs {
+    /// Play a built-in variant: classic, easy (3 digits), hex (4 of 16 symbols) or mastermind (4 pegs, 6 colors, 10 guesses)
+    #[arg(long, value_parser = PossibleValuesParser::new(ruleset::PRESET_NAMES), conflicts_with_all = VARIANT_OPTIONS)]
+    pub preset: Option<String>,
+    /// Read the variant from a TOML file (length, alphabet, repeats, feedback, max_guesses, no_leading, words, preset)
+    #[arg(long, conflicts_with_all = VARIANT_OPTIONS, conflicts_with = "preset")]
+    pub rules: Option<PathBuf>,
+    // the rules of the variant, made once
+    #[arg(skip)]
+    ruleset: OnceLock<Result<Ruleset, String>>,
+    /// Number of symbols in the secret
+    #[arg(long, default_value_t = 4)]
+    pub length: usize,
+    /// Symbols the secret is made of
+    #[arg(long, default_value = algos_utils::DEFAULT_ALPHABET)]
+    pub alphabet: String,
+    /// Allow the same symbol more than once in the secret and in guesses
+    #[arg(long)]
+    pub repeats: bool,
+    /// Play Mastermind with this many colors (R G B Y O P W K) instead of the alphabet, with repeats
+    #[arg(long, value_parser = clap::value_parser!(u64).range(2..=mastermind::COLORS.len() as u64), conflicts_with = "alphabet")]
+    pub colors: Option<u64>,
+    /// Give feedback as Pico-Fermi-Bagels clues ("Fermi Pico") instead of bulls and cows
+    #[arg(long)]
+    pub pico_fermi_bagels: bool,
+    /// What the feedback tells: bulls and cows, only the bulls, only their total, only whether there is a bull or a mark per symbol
+    #[arg(long, default_value = "bulls-and-cows", value_parser = PossibleValuesParser::new(FEEDBACK_KINDS.map(|kind| kind.get_name())), conflicts_with = "pico_fermi_bagels")]
+    pub feedback: String,
+    /// Play with the words of this file, one per line, instead of numbers: the words of --length letters
+    /// without a repeated letter are the secrets and the only guesses allowed
+    #[arg(long, conflicts_with_all = ["alphabet", "colors", "repeats"])]
+    pub words: Option<PathBuf>,
+    /// Maximum number of guesses, unlimited by default
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_guesses: Option<u64>,
+    /// Seed for the random number generator, for reproducible games
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+impl Default for VariantArgs {
+    fn default() -> VariantArgs {
+        return VariantArgs { preset: None, rules: None, ruleset: OnceLock::new(), length: 4, alphabet: String::from(algos_utils::DEFAULT_ALPHABET), repeats: false, colors: None, pico_fermi_bagels: false, feedback: String::from(FeedbackKind::default().get_name()), words: None, max_guesses: None, seed: None };
+    }
+}
+
+impl VariantArgs {
+    // the rules of the variant, exits with an error when they cannot be made
+    pub fn get_ruleset(&self) -> &Ruleset {
+        return self.try_get_ruleset().unwrap_or_else(|message| exit_with_error(&message));
+    }
+
+    fn try_get_ruleset(&self) -> Result<&Ruleset, String> {
+        let rules = self.ruleset.get_or_init(|| {
+            let rules = if let Some(path) = &self.rules {
+                Ruleset::load(path)?
+            } else if let Some(name) = &self.preset {
+                Ruleset::from_preset(name).ok_or(format!("unknown preset {}", name))?
+            } else {
+                self.make_ruleset()?
+            };
+            rules.validate()?;
+            if self.pico_fermi_bagels && rules.feedback_kind != FeedbackKind::BullsAndCows {
+                return Err(String::from("Pico-Fermi-Bagels clues need bulls and cows feedback"));
+            }
+            return Ok(rules);
+        });
+        return rules.as_ref().map_err(String::clone);
+    }
+
+    // the rules given by the options
+    fn make_ruleset(&self) -> Result<Ruleset, String> {
+        let feedback_kind = FeedbackKind::from_name(&self.feedback).ok_or(format!("unknown feedback {}", self.feedback))?;
+        if let Some(path) = &self.words {
+            if self.length == 0 {
+                return Err(String::from("length must be positive"));
+            }
+            let words = dictionary::load_words(path, self.length).map_err(|e| format!("cannot read words from {}: {}", path.display(), e))?;
+            let rules = Ruleset::from_words(words).ok_or(format!("{} has no word of {} different letters", path.display(), self.length))?;
+            return Ok(Ruleset { feedback_kind, max_guesses: self.max_guesses, ..rules });
+        }
+        // Mastermind always allows repeats
+        let rules = match self.colors {
+            Some(colors) => Ruleset::new(self.length, &mastermind::get_color_alphabet(colors as usize).unwrap(), true),
+            None => Ruleset::new(self.length, &self.alphabet.chars().collect::<Vec<char>>(), self.repeats),
+        };
+        return Ok(Ruleset { feedback_kind, max_guesses: self.max_guesses, ..rules });
+    }
+
+    pub fn get_length(&self) -> usize {
+        return self.get_ruleset().length;
+    }
+
+    pub fn get_alphabet(&self) -> Vec<char> {
+        return self.get_ruleset().alphabet.clone();
+    }
+
+    pub fn get_max_guesses(&self) -> Option<u64> {
+        return self.get_ruleset().max_guesses;
+    }
+
+    // "word", "code" or "number", what the secret is called
+    pub fn get_secret_name(&self) -> &'static str {
+        if self.get_ruleset().words.is_some() {
+            return "word";
+        }
+        return if self.is_mastermind() { "code" } else { "number" };
+    }
+
+    // colors with repeats, whether from --colors or a preset
+    pub fn is_mastermind(&self) -> bool {
+        let rules = self.get_ruleset();
+        return rules.repeats && rules.alphabet.len() >= 2 && mastermind::get_color_alphabet(rules.alphabet.len()).as_ref() == Some(&rules.alphabet);
+    }
+
+    pub fn get_feedback_style(&self) -> FeedbackStyle {
+        return if self.pico_fermi_bagels { FeedbackStyle::PicoFermiBagels } else { FeedbackStyle::BullsAndCows };
+    }
+
+    pub fn get_feedback_kind(&self) -> FeedbackKind {
+        return self.get_ruleset().feedback_kind;
+    }
+
+    // bulls and cows written as the variant writes them
+    pub fn format_feedback(&self, feedback: (usize, usize)) -> String {
+        if self.get_feedback_kind() == FeedbackKind::BullsAndCows {
+            return self.get_feedback_style().format_feedback(feedback);
+        }
+        return self.get_feedback_kind().format(feedback, self.get_length());
+    }
+
+    // characters of the longest feedback as `format_feedback` writes it, the marks are one per symbol
+    pub fn get_feedback_width(&self) -> usize {
+        let length = self.get_length();
+        return match self.get_feedback_kind() {
+            FeedbackKind::Positional => length.max(self.format_feedback((length, 0)).chars().count()),
+            kind => kind.generate_possible_feedbacks(length).into_iter()
+                .map(|feedback| self.format_feedback(feedback).chars().count()).max().unwrap_or(0),
+        };
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        return self.try_get_ruleset().map(|_| ());
+    }
+
+    // number of possible secrets, saturating on overflow
+    pub fn get_numbers_count(&self) -> u64 {
+        return self.get_ruleset().get_numbers_count();
+    }
+
+    // whether the solvers can play the variant
+    pub fn check_solvable(&self) -> Result<(), String> {
+        self.validate()?;
+        if self.get_numbers_count() > MAX_CANDIDATES {
+            return Err(format!("variant has {} possible secrets, at most {} are supported by the solvers", self.get_numbers_count(), MAX_CANDIDATES));
+        }
+        return Ok(());
+    }
+
+    pub fn generate_numbers(&self) -> Result<Vec<String>, String> {
+        self.check_solvable()?;
+        return Ok(self.get_ruleset().generate_numbers());
+    }
+
+    // a game of the variant with a random secret, None when the variant is not valid
+    pub fn new_game<R: rand::Rng>(&self, rng: &mut R) -> Option<Game> {
+        return Game::new_with_ruleset(self.try_get_ruleset().ok()?, rng);
+    }
+
+    // a game of the variant with a known secret, None when it is not a secret of the variant
+    pub fn new_game_with_answer(&self, answer: &str) -> Option<Game> {
+        return Game::new_with_ruleset_and_answer(self.try_get_ruleset().ok()?, answer);
+    }
+
+    pub fn make_rng(&self) -> StdRng {
+        return match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+    }
+}
+
+#[derive(Args, Clone, Debug, Default)]
+pub struct BudgetArgs {
+    /// Evaluations per guess: simulated games for mcts, scored guesses for the other searching solvers
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "time_limit")]
+    pub iterations: Option<u64>,
+    /// Milliseconds to think per guess, the best guess found so far is played
+    #[arg(long)]
+    pub time_limit: Option<u64>,
+}
+
+impl BudgetArgs {
+    pub fn get_budget(&self) -> Option<Budget> {
+        if let Some(iterations) = self.iterations {
+            return Some(Budget::Iterations(iterations as usize));
+        }
+        return self.time_limit.map(|milliseconds| Budget::Time(Duration::from_millis(milliseconds)));
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct SolverArgs {
+    /// Guessing algorithm
+    #[arg(long, default_value = "entropy", value_parser = PossibleValuesParser::new(algos::SOLVER_NAMES))]
+    pub solver: String,
+    #[command(flatten)]
+    pub budget: BudgetArgs,
+}
+
+impl SolverArgs {
+    pub fn create(&self, rules: &Ruleset, seed: Option<u64>) -> Result<Box<dyn Solver>, String> {
+        if self.solver == "optimal" && rules.get_numbers_count() > MAX_OPTIMAL_NUMBERS as u64 {
+            return Err(format!("{} possible secrets, the optimal solver is limited to {}", rules.get_numbers_count(), MAX_OPTIMAL_NUMBERS));
+        }
+        return algos::create_solver(&self.solver, rules, seed, self.budget.get_budget()).ok_or(format!("cannot create solver {}", self.solver));
+    }
+}
+
+// for commands comparing several solvers
+#[derive(Args, Clone, Debug)]
+pub struct SolverListArgs {
+    /// Guessing algorithms, separated by commas
+    #[arg(long = "solver", default_value = "entropy", value_delimiter = ',', value_parser = PossibleValuesParser::new(algos::SOLVER_NAMES))]
+    pub solvers: Vec<String>,
+    #[command(flatten)]
+    pub budget: BudgetArgs,
+}
+
+impl SolverListArgs {
+    pub fn get_solver_args(&self) -> Vec<SolverArgs> {
+        return self.solvers.iter().map(|solver| SolverArgs { solver: solver.clone(), budget: self.budget.clone() }).collect();
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    std::process::exit(2);
+}
@@ -0,0 +1,73 @@
+use std::io::{self, BufRead};
+use bullsncows::algos::multi_secret_algo::MultiSecretAlgo;
+use bullsncows::game::GameState;
+use bullsncows::multi::MultiGame;
+use crate::commands::{exit_with_error, VariantArgs};
+
+// the player, or the solver with `bot`, guesses the secrets of all the boards at once;
+// every guess prints a row of the board with the feedback of each secret
+pub fn run(variant: &VariantArgs, boards: usize, bot: bool) {
+    let numbers = variant.generate_numbers().unwrap_or_else(|message| exit_with_error(&message));
+    let mut rng = variant.make_rng();
+    let mut g = MultiGame::new_with_ruleset(boards, variant.get_ruleset(), &mut rng).unwrap_or_else(|| exit_with_error("at least one board is needed"));
+    let alphabet: String = variant.get_alphabet().iter().collect();
+    match variant.get_max_guesses() {
+        Some(max_guesses) => println!("Guess {} secrets at once ({} symbols from {}, {} guesses, \"quit\" to give up)", boards, variant.get_length(), alphabet, max_guesses),
+        None => println!("Guess {} secrets at once ({} symbols from {}, \"quit\" to give up)", boards, variant.get_length(), alphabet),
+    }
+
+    if bot {
+        let mut solver = MultiSecretAlgo::new(numbers, variant.get_ruleset(), boards).unwrap();
+        while !g.is_over() {
+            let guess = match solver.guess() {
+                Some(guess) => guess,
+                None => break,
+            };
+            let feedbacks = g.make_guess(&guess).unwrap();
+            println!("{}", format_row(variant, &guess, &feedbacks));
+            if solver.incorporate_guess_feedback(&feedbacks) != Some(true) {
+                exit_with_error("the feedback leaves a board without any possible secret");
+            }
+        }
+    } else {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+        while !g.is_over() {
+            let line = match lines.next() {
+                Some(line) => line.expect("Error while reading user input"),
+                None => "quit".to_string(),
+            };
+            let guess: &str = line.trim();
+            if guess == "quit" || guess == "q" {
+                g.abandon();
+                break;
+            }
+            match g.make_guess(guess) {
+                Some(feedbacks) => println!("{}", format_row(variant, guess, &feedbacks)),
+                None => println!("Invalid input!"),
+            }
+        }
+    }
+
+    match g.get_state() {
+        GameState::Won => println!("Solved all {} secrets after {} guesses", boards, g.get_guess_count()),
+        _ => {
+            println!("Solved {} of {} secrets, they were {}", g.get_solved_count(), boards, g.get_answers().join(" "));
+            if bot {
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+// "0123 | 1b 1c | 0b 2c |       | 4b 0c", the boards solved before the guess are left empty
+fn format_row(variant: &VariantArgs, guess: &str, feedbacks: &[Option<(usize, usize)>]) -> String {
+    let cells: Vec<String> = feedbacks.iter().map(|feedback| match feedback {
+        Some(feedback) => variant.format_feedback(*feedback),
+        None => String::new(),
+    }).collect();
+    // as wide as the longest feedback so that the columns line up from row to row
+    let width: usize = variant.get_feedback_width();
+    let row: Vec<String> = cells.iter().map(|cell| format!("{:<width$}", cell, width = width)).collect();
+    return format!("{} | {}", guess, row.join(" | ")).trim_end().to_string();
+}
@@ -0,0 +1,40 @@
+use serde_json::json;
+use bullsncows::algos::algos_utils;
+use bullsncows::algos::optimal_algo::{DecisionTree, OptimalSearch, MAX_OPTIMAL_NUMBERS};
+use crate::commands::{exit_with_error, OutputFormat, VariantArgs};
+
+// searches the strategy with the fewest guesses on average and prints its decision tree
+pub fn run(variant: &VariantArgs, candidates_only: bool, format: OutputFormat) {
+    let numbers = variant.generate_numbers().unwrap_or_else(|message| exit_with_error(&message));
+    if numbers.len() > MAX_OPTIMAL_NUMBERS {
+        exit_with_error(&format!("{} possible secrets, the search is limited to {}", numbers.len(), MAX_OPTIMAL_NUMBERS));
+    }
+    let max_length: usize = algos_utils::get_max_table_length(variant.get_feedback_kind());
+    if variant.get_length() > max_length {
+        exit_with_error(&format!("{} feedback is limited to {} symbols here", variant.get_feedback_kind().get_name(), max_length));
+    }
+    let tree = OptimalSearch::new(numbers, variant.get_ruleset(), candidates_only).unwrap().solve();
+    match format {
+        OutputFormat::Text => {
+            println!("average guesses: {:.4} ({} guesses for {} secrets)", tree.get_average_guesses(), tree.total_guesses, tree.secrets);
+            for line in tree.describe() {
+                println!("{}", line);
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", json!({
+                "secrets": tree.secrets,
+                "total_guesses": tree.total_guesses,
+                "average_guesses": tree.get_average_guesses(),
+                "tree": tree_to_json(&tree),
+            }));
+        }
+    }
+}
+
+fn tree_to_json(tree: &DecisionTree) -> serde_json::Value {
+    let branches: serde_json::Map<String, serde_json::Value> = tree.branches.iter()
+        .map(|(feedback, child)| (tree.format_feedback(*feedback), tree_to_json(child)))
+        .collect();
+    return json!({"guess": tree.guess, "secrets": tree.secrets, "branches": branches});
+}
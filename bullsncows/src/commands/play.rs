@@ -0,0 +1,166 @@
+use std::io::{self, BufRead, Read, Write};
+use rand::rngs::StdRng;
+use bullsncows::algos::feedback::FeedbackKind;
+use bullsncows::clues;
+use bullsncows::game::{Game, GameState, SessionStats};
+use bullsncows::hints::HINT_KINDS;
+use bullsncows::leaderboard::{GameRecord, ScoreStore};
+use bullsncows::mastermind;
+use bullsncows::wordle;
+use crate::commands::{exit_with_error, VariantArgs};
+
+// with a player name every finished game is saved to the score store
+pub fn run(variant: &VariantArgs, debug: bool, player: Option<&str>, store: &ScoreStore) {
+    if let Err(message) = variant.validate() {
+        exit_with_error(&message);
+    }
+    let mut rng = variant.make_rng();
+    let stdin = io::stdin();
+    let scores = player.map(|player| (player, store));
+    play_session(&mut stdin.lock(), &mut io::stdout(), variant, debug, scores, &mut rng).unwrap_or_else(|e| exit_with_error(&e.to_string()));
+}
+
+// longer lines are an error, so that a `serve` client cannot make a session buffer without bound
+const MAX_LINE: u64 = 4096;
+
+// None on end of input
+fn read_input_line<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.take(MAX_LINE).read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.len() as u64 == MAX_LINE && !line.ends_with('\n') {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("line longer than {} bytes", MAX_LINE)));
+    }
+    return Ok(Some(String::from(line.trim())));
+}
+
+// plays games until the player declines to play again, shared by `play` and `serve`
+pub fn play_session<R: BufRead, W: Write>(input: &mut R, output: &mut W, variant: &VariantArgs, debug: bool, scores: Option<(&str, &ScoreStore)>, rng: &mut StdRng) -> io::Result<SessionStats> {
+    let mut stats = SessionStats::new();
+    loop {
+        let g = play_game(input, output, variant, debug, rng)?;
+        stats.record(&g);
+        if let Some((player, store)) = scores {
+            if let Some(record) = GameRecord::from_game(player, &g) {
+                if let Err(e) = store.append(&record) {
+                    writeln!(output, "Cannot save the score to {}: {}", store.get_path().display(), e)?;
+                }
+            }
+        }
+        write_session_stats(output, &stats)?;
+
+        writeln!(output, "Play again? [y/n]")?;
+        output.flush()?;
+        match read_input_line(input)? {
+            Some(answer) if answer == "y" || answer == "yes" => continue,
+            _ => break,
+        }
+    }
+    return Ok(stats);
+}
+
+fn play_game<R: BufRead, W: Write>(input: &mut R, output: &mut W, variant: &VariantArgs, debug: bool, rng: &mut StdRng) -> io::Result<Game> {
+    let mut g = variant.new_game(rng).expect("Invalid variant");
+    let alphabet: String = variant.get_alphabet().iter().collect();
+    let secret_name: &str = variant.get_secret_name();
+    let goal: String = if variant.is_mastermind() {
+        format!("Break the code ({} pegs of colors {}", g.get_answer_length(), alphabet)
+    } else if let Some(words) = g.get_dictionary() {
+        format!("Guess the word ({} letters, one of {} words", g.get_answer_length(), words.len())
+    } else if g.get_ruleset().no_leading.is_empty() {
+        format!("Guess the number ({} symbols from {}", g.get_answer_length(), alphabet)
+    } else {
+        format!("Guess the number ({} symbols from {}, not starting with {}", g.get_answer_length(), alphabet, g.get_ruleset().no_leading.iter().collect::<String>())
+    };
+    match variant.get_max_guesses() {
+        Some(max_guesses) => writeln!(output, "{}, {} guesses, \"hint\" for help, \"quit\" to give up)", goal, max_guesses)?,
+        None => writeln!(output, "{}, \"hint\" for help, \"quit\" to give up)", goal)?,
+    }
+    if debug {
+        writeln!(output, "[debug] answer: {}", g.get_answer())?;
+    }
+    output.flush()?;
+
+    while !g.is_over() {
+        let user_guess = match read_input_line(input)? {
+            Some(line) => line,
+            None => {
+                g.abandon();
+                break;
+            }
+        };
+        if user_guess == "quit" || user_guess == "q" {
+            g.abandon();
+            break;
+        }
+        if let Some(level) = user_guess.strip_prefix("hint") {
+            write_hint(output, &mut g, variant, level.trim())?;
+            continue;
+        }
+
+        if variant.is_mastermind() {
+            // colors can be typed in lowercase
+            let user_guess = user_guess.to_uppercase();
+            match g.make_guess(&user_guess) {
+                None => writeln!(output, "Invalid input!")?,
+                Some(feedback) if g.get_feedback_kind() == FeedbackKind::Positional => writeln!(output, "{}  {}", wordle::render_marks(&user_guess, &g.get_marks(feedback).unwrap()), variant.format_feedback(feedback))?,
+                Some(feedback) if g.get_feedback_kind() != FeedbackKind::BullsAndCows => writeln!(output, "{}  {}", mastermind::render_guess(&user_guess), variant.format_feedback(feedback))?,
+                Some(feedback) => writeln!(output, "{}  {}  black: {}, white: {}", mastermind::render_guess(&user_guess),
+                    mastermind::render_key_pegs(feedback, g.get_answer_length()), feedback.0, feedback.1)?,
+            }
+            output.flush()?;
+            continue;
+        }
+        match g.make_guess(&user_guess) {
+            None => writeln!(output, "Invalid input!")?,
+            Some(feedback) if variant.pico_fermi_bagels => writeln!(output, "{}", clues::format_clues(feedback))?,
+            Some(feedback) if g.get_feedback_kind() == FeedbackKind::Positional => writeln!(output, "{}  {}", wordle::render_marks(&user_guess, &g.get_marks(feedback).unwrap()), variant.format_feedback(feedback))?,
+            Some(feedback) if g.get_feedback_kind() != FeedbackKind::BullsAndCows => writeln!(output, "{}", variant.format_feedback(feedback))?,
+            Some((bulls, cows)) => writeln!(output, "Bulls: {}, cows: {}", bulls, cows)?,
+        }
+        output.flush()?;
+    }
+
+    match g.get_state() {
+        GameState::Won => {
+            writeln!(output, "You won after {} guesses", g.get_guess_count())?;
+            writeln!(output, "Score: {} ({} hints used, {}s, difficulty {:.2})", g.get_score(), g.get_hints().len(), g.get_elapsed().as_secs(), g.get_difficulty())?;
+        }
+        GameState::Lost => writeln!(output, "Out of guesses! The {} was {}", secret_name, g.get_answer())?,
+        GameState::Abandoned => writeln!(output, "Game abandoned. The {} was {}", secret_name, g.get_answer())?,
+        GameState::InProgress => {}
+    }
+    return Ok(g);
+}
+
+// "hint" lists the hints, "hint <n>" asks for the n-th one;
+// hints look at every possible secret, so they are only given for the variants the solvers support
+fn write_hint<W: Write>(output: &mut W, g: &mut Game, variant: &VariantArgs, level: &str) -> io::Result<()> {
+    let kind = match level.parse::<usize>() {
+        Ok(level) if level >= 1 && level <= HINT_KINDS.len() => HINT_KINDS[level - 1],
+        _ => {
+            for (i, kind) in HINT_KINDS.iter().enumerate() {
+                writeln!(output, "hint {}: {} (costs {} points)", i + 1, kind.get_description(), kind.cost())?;
+            }
+            return Ok(());
+        }
+    };
+    if let Err(message) = variant.check_solvable() {
+        writeln!(output, "No hints for this variant: {}", message)?;
+        return Ok(());
+    }
+    match g.request_hint(kind) {
+        Some(hint) => writeln!(output, "Hint: {}", hint)?,
+        None => writeln!(output, "No such hint would tell you anything new")?,
+    }
+    return Ok(());
+}
+
+fn write_session_stats<W: Write>(output: &mut W, stats: &SessionStats) -> io::Result<()> {
+    write!(output, "Games: {}, won: {}, lost: {}, abandoned: {}", stats.games_played, stats.games_won, stats.games_lost, stats.games_abandoned)?;
+    if let Some(average) = stats.get_average_guesses_to_win() {
+        write!(output, ", average guesses to win: {:.2}, best: {}", average, stats.best_guess_count.unwrap())?;
+    }
+    return writeln!(output);
+}
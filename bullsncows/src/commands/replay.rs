@@ -0,0 +1,68 @@
+use std::path::Path;
+use serde_json::json;
+use bullsncows::algos::feedback::FeedbackKind;
+use bullsncows::transcript;
+use crate::commands::{exit_with_error, OutputFormat, VariantArgs};
+
+// without a secret (neither given nor in the "Number to guess" header) lists all secrets of the variant consistent with it
+pub fn run(variant: &VariantArgs, path: &Path, secret: Option<String>, format: OutputFormat) {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| exit_with_error(&format!("cannot read {}: {}", path.display(), e)));
+    let parsed = transcript::parse_transcript_of_kind(&text, variant.get_feedback_kind()).unwrap_or_else(|e| exit_with_error(&e.to_string()));
+
+    match secret.or(parsed.secret.clone()) {
+        Some(secret) => {
+            let mismatches = parsed.check_against_secret(&secret);
+            match format {
+                OutputFormat::Text => {
+                    // with the length of the guess, which may not be the variant's
+                    let format_feedback = |feedback: (usize, usize), guess: &str| -> String {
+                        if variant.get_feedback_kind() == FeedbackKind::BullsAndCows {
+                            return variant.format_feedback(feedback);
+                        }
+                        return variant.get_feedback_kind().format(feedback, guess.chars().count());
+                    };
+                    for mismatch in mismatches.iter() {
+                        let actual = match mismatch.actual {
+                            Some(feedback) => format_feedback(feedback, &mismatch.entry.guess),
+                            None => String::from("not comparable"),
+                        };
+                        println!("line {}: {} recorded {}, actual {}", mismatch.entry.line, mismatch.entry.guess,
+                            format_feedback(mismatch.entry.feedback, &mismatch.entry.guess), actual);
+                    }
+                    println!("{} of {} lines consistent with secret {}", parsed.entries.len() - mismatches.len(), parsed.entries.len(), secret);
+                }
+                OutputFormat::Json => {
+                    let mismatches_json: Vec<serde_json::Value> = mismatches.iter().map(|mismatch| json!({
+                        "line": mismatch.entry.line,
+                        "guess": mismatch.entry.guess,
+                        "recorded": [mismatch.entry.feedback.0, mismatch.entry.feedback.1],
+                        "actual": mismatch.actual.map(|feedback| [feedback.0, feedback.1]),
+                    })).collect();
+                    println!("{}", json!({"secret": secret, "lines": parsed.entries.len(), "mismatches": mismatches_json}));
+                }
+            }
+            if !mismatches.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        None => {
+            if let Some(length) = parsed.get_guess_length().filter(|&length| length != variant.get_length()) {
+                exit_with_error(&format!("the guesses have {} symbols, the variant {}, see --length", length, variant.get_length()));
+            }
+            let numbers = variant.generate_numbers().unwrap_or_else(|message| exit_with_error(&message));
+            let secrets = parsed.find_consistent_secrets(numbers);
+            match format {
+                OutputFormat::Text => {
+                    for secret in secrets.iter() {
+                        println!("{}", secret);
+                    }
+                    println!("{} consistent secrets", secrets.len());
+                }
+                OutputFormat::Json => println!("{}", json!({"consistent_secrets": secrets})),
+            }
+            if secrets.is_empty() {
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use clap::Subcommand;
+use bullsncows::leaderboard::{self, ScoreStore};
+use crate::commands::{exit_with_error, VariantArgs};
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ScoresQuery {
+    /// Best game of a player in every variant they played
+    Best {
+        /// Player name used with `play --player`
+        #[arg(long)]
+        player: String,
+    },
+    /// Best score of every player in one variant
+    Leaderboard {
+        #[command(flatten)]
+        variant: VariantArgs,
+        /// Number of players to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// How many guesses the won games took
+    Histogram {
+        /// Only games of this player
+        #[arg(long)]
+        player: Option<String>,
+        /// Only games of the variant given by --length, --alphabet and --repeats
+        #[arg(long)]
+        this_variant: bool,
+        #[command(flatten)]
+        variant: VariantArgs,
+    },
+}
+
+pub fn run(query: &ScoresQuery, store: &ScoreStore) {
+    let records = store.load().unwrap_or_else(|e| exit_with_error(&format!("cannot read {}: {}", store.get_path().display(), e)));
+
+    match query {
+        ScoresQuery::Best { player } => {
+            let bests = leaderboard::personal_bests(&records, player);
+            if bests.is_empty() {
+                println!("No won games of {}", player);
+            }
+            for record in bests.iter() {
+                println!("{:<24} score {:>5}, {} guesses, {} hints, {}s", record.variant, record.score, record.guesses, record.hints, record.seconds);
+            }
+        }
+        ScoresQuery::Leaderboard { variant, limit } => {
+            let key = variant.get_ruleset().get_key();
+            println!("Leaderboard {}", key);
+            for (i, record) in leaderboard::leaderboard(&records, &key, *limit).iter().enumerate() {
+                println!("{:>3}. {:<20} {:>5} ({} guesses, {} hints, {}s)", i + 1, record.player, record.score, record.guesses, record.hints, record.seconds);
+            }
+        }
+        ScoresQuery::Histogram { player, this_variant, variant } => {
+            let key = variant.get_ruleset().get_key();
+            let distribution = leaderboard::guess_count_distribution(&records, player.as_deref(), if *this_variant { Some(key.as_str()) } else { None });
+            let most = distribution.values().copied().max().unwrap_or(0);
+            for (guesses, count) in distribution.iter() {
+                let bar_length = (count * 40).div_ceil(most);
+                println!("{:>3} guesses {:>5} {}", guesses, count, "#".repeat(bar_length));
+            }
+        }
+    }
+}
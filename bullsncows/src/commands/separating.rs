@@ -0,0 +1,43 @@
+use serde_json::json;
+use bullsncows::algos::algos_utils;
+use bullsncows::algos::budget::Budget;
+use bullsncows::algos::separating_set::{SeparatingSetSearch, MAX_SEPARATING_NUMBERS};
+use crate::commands::{exit_with_error, BudgetArgs, OutputFormat, VariantArgs};
+
+// guesses scored by the search when no budget is given, about two seconds for the classic 3 digit game
+const DEFAULT_ITERATIONS: usize = 300_000;
+
+// searches the fewest guesses that, submitted all at once, tell every secret apart by their feedbacks
+pub fn run(variant: &VariantArgs, budget: &BudgetArgs, format: OutputFormat) {
+    let numbers = variant.generate_numbers().unwrap_or_else(|message| exit_with_error(&message));
+    if numbers.len() > MAX_SEPARATING_NUMBERS {
+        exit_with_error(&format!("{} possible secrets, the search is limited to {}", numbers.len(), MAX_SEPARATING_NUMBERS));
+    }
+    let max_length: usize = algos_utils::get_max_table_length(variant.get_feedback_kind());
+    if variant.get_length() > max_length {
+        exit_with_error(&format!("{} feedback is limited to {} symbols here", variant.get_feedback_kind().get_name(), max_length));
+    }
+    let secrets: usize = numbers.len();
+    let mut search = SeparatingSetSearch::new(numbers, variant.get_ruleset()).unwrap();
+    let set = search.solve(Some(budget.get_budget().unwrap_or(Budget::Iterations(DEFAULT_ITERATIONS))));
+    match format {
+        OutputFormat::Text => {
+            if set.is_smallest() {
+                println!("{} guesses tell all {} secrets apart, no fewer can", set.guesses.len(), secrets);
+            } else {
+                println!("{} guesses tell all {} secrets apart, at least {} are needed", set.guesses.len(), secrets, set.lower_bound);
+            }
+            for guess in set.guesses.iter() {
+                println!("{}", guess);
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", json!({
+                "secrets": secrets,
+                "guesses": set.guesses,
+                "lower_bound": set.lower_bound,
+                "smallest": set.is_smallest(),
+            }));
+        }
+    }
+}
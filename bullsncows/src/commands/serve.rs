@@ -0,0 +1,44 @@
+use std::io::{BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use rand::{rngs::StdRng, SeedableRng};
+use crate::commands::{exit_with_error, play, VariantArgs};
+
+// every connection gets its own `play` session in a separate thread
+pub fn run(variant: &VariantArgs, host: &str, port: u16, debug: bool) {
+    if let Err(message) = variant.validate() {
+        exit_with_error(&message);
+    }
+    let listener = TcpListener::bind((host, port)).unwrap_or_else(|e| exit_with_error(&format!("cannot listen on {}:{}: {}", host, port, e)));
+    println!("Listening on {}", listener.local_addr().unwrap());
+    std::io::stdout().flush().unwrap();
+
+    for (i, stream) in listener.incoming().enumerate() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("connection failed: {}", e);
+                continue;
+            }
+        };
+        let variant = variant.clone();
+        let rng = match variant.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(i as u64)),
+            None => StdRng::from_entropy(),
+        };
+        thread::spawn(move || serve_connection(stream, &variant, debug, rng));
+    }
+}
+
+fn serve_connection(stream: TcpStream, variant: &VariantArgs, debug: bool, mut rng: StdRng) {
+    let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+    let mut input = match stream.try_clone() {
+        Ok(reader) => BufReader::new(reader),
+        Err(_) => return,
+    };
+    let mut output = stream;
+    match play::play_session(&mut input, &mut output, variant, debug, None, &mut rng) {
+        Ok(stats) => println!("{}: {} games, {} won", peer, stats.games_played, stats.games_won),
+        Err(e) => eprintln!("{}: {}", peer, e),
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, IsTerminal};
+use serde_json::json;
+use bullsncows::algos::Solver;
+use bullsncows::algos::feedback::{FeedbackKind, Mark};
+use bullsncows::game::{Game, GameState};
+use bullsncows::mastermind;
+use bullsncows::wordle;
+use crate::commands::{exit_with_error, OutputFormat, SolverArgs, VariantArgs};
+
+pub fn run(variant: &VariantArgs, solver_args: &SolverArgs, secret: Option<String>, format: OutputFormat) {
+    variant.check_solvable().unwrap_or_else(|message| exit_with_error(&message));
+    let mut g = match secret {
+        Some(secret) => variant.new_game_with_answer(&secret)
+            .unwrap_or_else(|| exit_with_error(&format!("secret {} does not match the variant", secret))),
+        None => variant.new_game(&mut variant.make_rng()).unwrap(),
+    };
+    let mut solver = solver_args.create(variant.get_ruleset(), variant.seed).unwrap_or_else(|message| exit_with_error(&message));
+
+    let history = play_solver_game(solver.as_mut(), &mut g);
+    let solved = g.get_state() == GameState::Won;
+
+    match format {
+        OutputFormat::Text => {
+            println!("Number to guess: {}", g.get_answer());
+            println!();
+            // colored pegs or marks on a terminal, a transcript that `replay` reads otherwise
+            let render_pegs: bool = variant.is_mastermind() && variant.get_feedback_kind() == FeedbackKind::BullsAndCows && io::stdout().is_terminal();
+            let render_marks: bool = variant.get_feedback_kind() == FeedbackKind::Positional && io::stdout().is_terminal();
+            for (guess, feedback) in history.iter() {
+                if render_pegs {
+                    println!("{}  {}", mastermind::render_guess(guess), mastermind::render_key_pegs(*feedback, variant.get_length()));
+                } else if render_marks {
+                    println!("{}  {}", wordle::render_marks(guess, &g.get_marks(*feedback).unwrap()), variant.format_feedback(*feedback));
+                } else {
+                    println!("{} {}", guess, variant.format_feedback(*feedback));
+                }
+            }
+            if !solved {
+                eprintln!("Not solved after {} guesses", g.get_guess_count());
+            }
+        }
+        OutputFormat::Json => {
+            let guesses: Vec<serde_json::Value> = history.iter().map(|(guess, feedback)| {
+                match g.get_marks(*feedback) {
+                    Some(marks) => {
+                        let cows: usize = marks.iter().filter(|&&mark| mark == Mark::Cow).count();
+                        json!({"guess": guess, "bulls": feedback.0, "cows": cows, "marks": marks.iter().map(|mark| mark.to_char()).collect::<String>()})
+                    }
+                    None => json!({"guess": guess, "bulls": feedback.0, "cows": feedback.1}),
+                }
+            }).collect();
+            println!("{}", json!({
+                "secret": g.get_answer(),
+                "solver": solver_args.solver,
+                "solved": solved,
+                "guess_count": g.get_guess_count(),
+                "guesses": guesses,
+            }));
+        }
+    }
+    if !solved {
+        std::process::exit(1);
+    }
+}
+
+// lets the solver play until the game is over or it runs out of candidates
+pub fn play_solver_game(solver: &mut dyn Solver, g: &mut Game) -> Vec<(String, (usize, usize))> {
+    let mut history: Vec<(String, (usize, usize))> = Vec::new();
+    while !g.is_over() {
+        let guess = match solver.guess() {
+            Some(guess) => guess,
+            None => break,
+        };
+        // the game may reject symbols the solver was given, nothing more can be done then
+        let feedback = match g.make_guess(&guess) {
+            Some(feedback) => feedback,
+            None => break,
+        };
+        history.push((guess, feedback));
+        solver.incorporate_guess_feedback(feedback.0, feedback.1);
+    }
+    return history;
+}
@@ -0,0 +1,285 @@
+use std::io::{self, Write};
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use bullsncows::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+use bullsncows::algos::feedback::FeedbackKind;
+use bullsncows::algos::knowledge::Knowledge;
+use bullsncows::clues;
+use bullsncows::game::{Game, GameState};
+use bullsncows::mastermind;
+use bullsncows::wordle;
+use crate::commands::{exit_with_error, VariantArgs};
+
+// Full screen game: the player guesses while an entropy solver follows along, showing
+// how many secrets are still possible, where each symbol can still be and what it would guess.
+
+struct TuiState {
+    variant: VariantArgs,
+    numbers: Vec<String>,
+    game: Game,
+    tracker: DepthOneEntropyAlgo,
+    history: Vec<(String, (usize, usize))>,
+    suggestion: Option<(String, f64)>,
+    input: String,
+    message: String,
+}
+
+impl TuiState {
+    fn new(variant: &VariantArgs, numbers: Vec<String>, rng: &mut rand::rngs::StdRng) -> TuiState {
+        let game = variant.new_game(rng).unwrap();
+        let tracker = DepthOneEntropyAlgo::new(numbers.clone(), variant.get_ruleset()).unwrap();
+        let suggestion = tracker.suggest_guess();
+        return TuiState { variant: variant.clone(), numbers, game, tracker, history: Vec::new(), suggestion, input: String::new(), message: String::new() };
+    }
+
+    fn submit_guess(&mut self) {
+        let guess = std::mem::take(&mut self.input);
+        match self.game.make_guess(&guess) {
+            None => self.message = format!("{} is not a valid guess", guess),
+            Some((bulls, cows)) => {
+                self.history.push((guess.clone(), (bulls, cows)));
+                self.tracker.incorporate_external_guess_feedback(guess, bulls, cows);
+                self.suggestion = if self.game.is_over() { None } else { self.tracker.suggest_guess() };
+                self.message = match self.game.get_state() {
+                    GameState::Won => format!("You won after {} guesses! [n] new game, [q] quit", self.game.get_guess_count()),
+                    GameState::Lost => format!("Out of guesses, the {} was {}. [n] new game, [q] quit", self.variant.get_secret_name(), self.game.get_answer()),
+                    _ => String::new(),
+                };
+            }
+        }
+    }
+
+    fn get_knowledge(&self) -> Option<Knowledge> {
+        return Knowledge::from_candidates(self.tracker.get_numbers(), self.variant.get_ruleset());
+    }
+}
+
+// the terminal is in raw mode on the alternate screen while it lives, and given back when it is dropped,
+// also when the game ends with an error or a panic
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        return Ok(guard);
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // nothing else can be done when the terminal cannot be restored
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub fn run(variant: &VariantArgs) {
+    let numbers = variant.generate_numbers().unwrap_or_else(|message| exit_with_error(&message));
+    let mut rng = variant.make_rng();
+    let mut state = TuiState::new(variant, numbers, &mut rng);
+
+    let mut stdout = io::stdout();
+    let guard = TerminalGuard::enter().expect("Cannot switch the terminal to raw mode");
+    let result = event_loop(&mut stdout, &mut state, &mut rng);
+    drop(guard);
+    result.expect("Terminal error");
+}
+
+// characters of the longest line of the guess history, see `draw`
+fn get_history_width(variant: &VariantArgs) -> usize {
+    let length = variant.get_length();
+    // the number of the guess, "  1. "
+    let numbering: usize = 5;
+    if variant.get_feedback_kind() == FeedbackKind::Positional {
+        // a cell of three characters per symbol
+        return numbering + 3 * length;
+    }
+    if variant.get_feedback_kind() == FeedbackKind::BullsAndCows && variant.is_mastermind() {
+        // pegs separated by spaces, then a key peg per peg
+        return numbering + (2 * length - 1) + 2 + length;
+    }
+    return numbering + length + 2 + variant.get_feedback_width();
+}
+
+fn event_loop<W: Write>(out: &mut W, state: &mut TuiState, rng: &mut rand::rngs::StdRng) -> io::Result<()> {
+    loop {
+        draw(out, state)?;
+        let key = match event::read()? {
+            Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) => (code, modifiers),
+            _ => continue,
+        };
+        if key == (KeyCode::Char('c'), KeyModifiers::CONTROL) || key.0 == KeyCode::Esc {
+            return Ok(());
+        }
+
+        if state.game.is_over() {
+            match key.0 {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char('n') => *state = TuiState::new(&state.variant, state.numbers.clone(), rng),
+                _ => {}
+            }
+            continue;
+        }
+        match key.0 {
+            KeyCode::Enter => state.submit_guess(),
+            KeyCode::Backspace => {
+                state.input.pop();
+            }
+            KeyCode::Tab => {
+                if let Some((guess, _)) = &state.suggestion {
+                    state.input = guess.clone();
+                }
+            }
+            KeyCode::Char(c) if state.input.chars().count() < state.variant.get_length() => {
+                // colors can be typed in lowercase
+                state.input.push(if state.variant.is_mastermind() { c.to_ascii_uppercase() } else { c });
+                state.message.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw<W: Write>(out: &mut W, state: &TuiState) -> io::Result<()> {
+    let alphabet = state.variant.get_alphabet();
+    queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+    let title: String = if state.variant.is_mastermind() {
+        format!("Mastermind: {} pegs of colors {}", state.variant.get_length(), alphabet.iter().collect::<String>())
+    } else {
+        format!("Bulls and cows: {} symbols from {}", state.variant.get_length(), alphabet.iter().collect::<String>())
+    };
+    queue!(out, SetAttribute(Attribute::Bold), Print(title), SetAttribute(Attribute::Reset))?;
+    if let Some(max_guesses) = state.variant.get_max_guesses() {
+        queue!(out, Print(format!("   guesses left: {}", max_guesses - state.game.get_guess_count())))?;
+    }
+
+    // left column: guess history
+    let mut row: u16 = 2;
+    queue!(out, cursor::MoveTo(0, row), SetAttribute(Attribute::Underlined), Print("History"), SetAttribute(Attribute::Reset))?;
+    for (i, (guess, (bulls, cows))) in state.history.iter().enumerate() {
+        row += 1;
+        if let Some(marks) = state.game.get_marks((*bulls, *cows)) {
+            queue!(out, cursor::MoveTo(0, row), Print(format!("{:>3}. {}", i + 1, wordle::render_marks(guess, &marks))))?;
+            continue;
+        }
+        if state.variant.get_feedback_kind() != FeedbackKind::BullsAndCows {
+            queue!(out, cursor::MoveTo(0, row), Print(format!("{:>3}. {}  ", i + 1, guess)),
+                SetForegroundColor(Color::Green), Print(state.variant.format_feedback((*bulls, *cows))), ResetColor)?;
+            continue;
+        }
+        if state.variant.is_mastermind() {
+            queue!(out, cursor::MoveTo(0, row), Print(format!("{:>3}. {}  {}", i + 1, mastermind::render_guess(guess), mastermind::render_key_pegs((*bulls, *cows), state.variant.get_length()))))?;
+            continue;
+        }
+        if state.variant.pico_fermi_bagels {
+            queue!(out, cursor::MoveTo(0, row), Print(format!("{:>3}. {}  ", i + 1, guess)),
+                SetForegroundColor(Color::Green), Print(clues::format_clues((*bulls, *cows))), ResetColor)?;
+            continue;
+        }
+        queue!(out, cursor::MoveTo(0, row), Print(format!("{:>3}. {}  ", i + 1, guess)),
+            SetForegroundColor(Color::Green), Print(format!("{}b ", bulls)),
+            SetForegroundColor(Color::Yellow), Print(format!("{}c", cows)), ResetColor)?;
+    }
+
+    // right column: candidates, position grid and suggestion, beside the longest history line
+    let column: u16 = u16::try_from(get_history_width(&state.variant) + 4).unwrap_or(u16::MAX);
+    let mut right_row: u16 = 2;
+    queue!(out, cursor::MoveTo(column, right_row), Print(format!("Possible secrets: {}", state.tracker.get_numbers_count())))?;
+    right_row += 2;
+    queue!(out, cursor::MoveTo(column, right_row), Print("pos "))?;
+    for symbol in alphabet.iter() {
+        queue!(out, Print(format!("{} ", symbol)))?;
+    }
+    let knowledge = state.get_knowledge();
+    for position in 0..state.variant.get_length() {
+        let possible = knowledge.as_ref().map(|knowledge| knowledge.get_possible_symbols(position)).unwrap_or_default();
+        right_row += 1;
+        queue!(out, cursor::MoveTo(column, right_row), Print(format!("{:>3} ", position + 1)))?;
+        for symbol in alphabet.iter() {
+            if knowledge.as_ref().is_some_and(|knowledge| knowledge.present.contains(symbol)) && possible.contains(symbol) {
+                queue!(out, SetForegroundColor(Color::Green), SetAttribute(Attribute::Bold), Print("o "), SetAttribute(Attribute::Reset), ResetColor)?;
+            } else if possible.contains(symbol) {
+                queue!(out, SetForegroundColor(Color::Green), Print("o "), ResetColor)?;
+            } else {
+                queue!(out, SetForegroundColor(Color::DarkGrey), Print(". "), ResetColor)?;
+            }
+        }
+    }
+    if let Some(knowledge) = &knowledge {
+        right_row += 2;
+        queue!(out, cursor::MoveTo(column, right_row), SetForegroundColor(Color::Green), Print(format!("in:  {}", knowledge.present.iter().collect::<String>())), ResetColor)?;
+        right_row += 1;
+        queue!(out, cursor::MoveTo(column, right_row), SetForegroundColor(Color::DarkGrey), Print(format!("out: {}", knowledge.absent.iter().collect::<String>())), ResetColor)?;
+    }
+    right_row += 2;
+    if let Some((guess, entropy)) = &state.suggestion {
+        queue!(out, cursor::MoveTo(column, right_row), Print(format!("Suggested guess: {} ({:.2} bits)", guess, entropy)))?;
+        if let Some(knowledge) = &knowledge {
+            right_row += 1;
+            queue!(out, cursor::MoveTo(column, right_row), SetForegroundColor(Color::DarkGrey), Print(knowledge.explain_guess(guess)), ResetColor)?;
+        }
+    }
+
+    let prompt_row = row.max(right_row) + 2;
+    queue!(out, cursor::MoveTo(0, prompt_row), Print(format!("> {}", state.input)))?;
+    queue!(out, cursor::MoveTo(0, prompt_row + 1), SetForegroundColor(Color::Cyan), Print(&state.message), ResetColor)?;
+    queue!(out, cursor::MoveTo(0, prompt_row + 3), SetForegroundColor(Color::DarkGrey),
+        Print("[Enter] guess  [Tab] take suggestion  [Backspace] delete  [Esc] quit"), ResetColor)?;
+    return out.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use super::TuiState;
+    use crate::commands::VariantArgs;
+
+    #[test]
+    fn test_tui_state_submit_guess() {
+        let variant = VariantArgs { length: 3, ..VariantArgs::default() };
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut state = TuiState::new(&variant, variant.generate_numbers().unwrap(), &mut rng);
+        assert_eq!(state.tracker.get_numbers_count(), 720);
+        assert!(state.suggestion.is_some());
+
+        state.input = String::from("11");
+        state.submit_guess();
+        assert_eq!(state.history.len(), 0);
+        assert!(!state.message.is_empty());
+
+        let answer = String::from(state.game.get_answer());
+        let guess: String = ["012", "345", "678"].iter().find(|&&guess| guess != answer).unwrap().to_string();
+        state.input = guess.clone();
+        state.submit_guess();
+        assert_eq!(state.history.len(), 1);
+        assert!(state.tracker.get_numbers().contains(&answer));
+        assert!(!state.tracker.get_numbers().contains(&guess));
+
+        let knowledge = state.get_knowledge().unwrap();
+        for (position, symbol) in answer.chars().enumerate() {
+            assert!(knowledge.get_possible_symbols(position).contains(&symbol));
+        }
+
+        state.input = answer;
+        state.submit_guess();
+        assert!(state.game.is_over());
+        assert!(state.suggestion.is_none());
+    }
+    #[test]
+    fn test_get_history_width() {
+        use super::get_history_width;
+        // "  1. 0123  4b 0c"
+        assert_eq!(get_history_width(&VariantArgs::default()), 16);
+        // "  1. 0123456789  10b 0c"
+        assert_eq!(get_history_width(&VariantArgs { length: 10, ..VariantArgs::default() }), 23);
+        // "  1. R G B Y  ●●○·"
+        assert_eq!(get_history_width(&VariantArgs { colors: Some(6), ..VariantArgs::default() }), 18);
+        // a cell per symbol
+        assert_eq!(get_history_width(&VariantArgs { length: 10, feedback: String::from("positional"), ..VariantArgs::default() }), 35);
+        // "  1. 0123  Fermi Fermi Fermi Fermi"
+        assert_eq!(get_history_width(&VariantArgs { pico_fermi_bagels: true, ..VariantArgs::default() }), 34);
+    }
+}
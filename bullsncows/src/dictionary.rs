@@ -0,0 +1,48 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Words instead of numbers: a word list gives the secrets and the only guesses allowed.
+// Like numbers without repeats, only isograms (words without a repeated letter) of the
+// chosen length are kept. Words are read one per line, ignoring case; lines with anything
+// but ASCII letters, like names with accents or phrases, are left out.
+
+// the isograms of the length in the text, lowercase, sorted and without duplicates
+pub fn parse_words(text: &str, length: usize) -> Vec<String> {
+    let words: BTreeSet<String> = text.lines()
+        .map(|line| line.trim().to_ascii_lowercase())
+        .filter(|word| word.len() == length && word.chars().all(|c| c.is_ascii_alphabetic()) && is_isogram(word))
+        .collect();
+    return words.into_iter().collect();
+}
+
+pub fn load_words(path: &Path, length: usize) -> io::Result<Vec<String>> {
+    return Ok(parse_words(&fs::read_to_string(path)?, length));
+}
+
+pub fn is_isogram(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().collect();
+    return (1..letters.len()).all(|i| !letters[..i].contains(&letters[i]));
+}
+
+// the letters used by the words, in order
+pub fn get_alphabet(words: &[String]) -> Vec<char> {
+    let letters: BTreeSet<char> = words.iter().flat_map(|word| word.chars()).collect();
+    return letters.into_iter().collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_alphabet, is_isogram, parse_words};
+
+    #[test]
+    fn test_parse_words() {
+        let words = parse_words("Cows\nbulls\n  farm \nmoon\nfarm\nbarn's\nhorse\nfork\n", 4);
+        assert_eq!(words, vec!["cows", "farm", "fork"]);
+        assert_eq!(parse_words("horse\n", 5), vec!["horse"]);
+        assert!(parse_words("", 4).is_empty());
+        assert!(is_isogram("word") && !is_isogram("moon"));
+        assert_eq!(get_alphabet(&words), vec!['a', 'c', 'f', 'k', 'm', 'o', 'r', 's', 'w']);
+    }
+}
@@ -1,21 +1,98 @@
-use rand::{seq::IteratorRandom, thread_rng};
-use std::collections::HashSet;
+use rand::{thread_rng, Rng};
+use std::time::{Duration, Instant};
+use crate::algos::algos_utils;
+use crate::algos::feedback::{FeedbackKind, Mark};
+use crate::hints::{self, Hint, HintKind};
+use crate::ruleset::Ruleset;
+
+// score of a classic game won at the first guess without hints, every further guess costs GUESS_PENALTY
+// and every TIME_PENALTY_SECONDS of play one point
+pub const MAX_SCORE: u64 = 1000;
+pub const GUESS_PENALTY: u64 = 100;
+pub const TIME_PENALTY_SECONDS: u64 = 10;
+// number of possible secrets of the classic 4 digit game
+const CLASSIC_NUMBERS_COUNT: f64 = 5040.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameState {
+    InProgress,
+    Won,
+    Lost,
+    Abandoned,
+}
 
 pub struct Game {
     guess_count : u64,
-    answer : String
+    answer : String,
+    rules : Ruleset,
+    state : GameState,
+    history : Vec<(String, (usize, usize))>,
+    hints : Vec<Hint>,
+    started_at : Instant,
+    finished_at : Option<Instant>,
 }
 
 impl Game {
     pub fn new(length : usize) -> Game {
-        let mut rng = thread_rng();
-        let sample = (0..10).choose_multiple(&mut rng, length);
+        return Game::new_with_guess_limit(length, None);
+    }
+
+    // max_guesses == None means the game only ends when the player wins or gives up
+    pub fn new_with_guess_limit(length : usize, max_guesses : Option<u64>) -> Game {
+        let alphabet: Vec<char> = algos_utils::DEFAULT_ALPHABET.chars().collect();
+        return Game::new_with_options(length, &alphabet, false, max_guesses, &mut thread_rng()).expect("Gamee error");
+    }
+
+    // returns None when no answer can be drawn: empty or duplicated alphabet,
+    // zero length or more positions than symbols without repeats
+    pub fn new_with_options<R: Rng>(length : usize, alphabet : &[char], repeats_allowed : bool, max_guesses : Option<u64>, rng : &mut R) -> Option<Game> {
+        return Game::new_with_ruleset(&Ruleset { max_guesses, ..Ruleset::new(length, alphabet, repeats_allowed) }, rng);
+    }
 
-        let answer = sample.iter().map(|u| -> char {
-                char::from_digit(*u, 10).expect("Gamee error")
-            }).collect();
+    // a random secret of the rules, None when they are not valid
+    pub fn new_with_ruleset<R: Rng>(rules : &Ruleset, rng : &mut R) -> Option<Game> {
+        let answer = rules.choose_secret(rng)?;
+        return Game::new_with_ruleset_and_answer(rules, &answer);
+    }
+
+    // None unless the answer is a possible secret of the rules
+    pub fn new_with_ruleset_and_answer(rules : &Ruleset, answer : &str) -> Option<Game> {
+        if rules.validate().is_err() || !rules.is_valid(answer) {
+            return None;
+        }
+        return Some(Game { guess_count: 0, answer: String::from(answer), rules: rules.clone(), state: GameState::InProgress, history: Vec::new(), hints: Vec::new(), started_at: Instant::now(), finished_at: None });
+    }
+
+    // game with a known answer, e.g. chosen by the player for the bot to guess
+    pub fn new_with_answer(answer : &str, alphabet : &[char], repeats_allowed : bool, max_guesses : Option<u64>) -> Option<Game> {
+        return Game::new_with_ruleset_and_answer(&Ruleset { max_guesses, ..Ruleset::new(answer.chars().count(), alphabet, repeats_allowed) }, answer);
+    }
 
-        return Game { guess_count: 0, answer };
+    // a random word of the list, see `dictionary`; None without words or when they differ in length or repeat letters
+    pub fn new_with_words<R: Rng>(words: &[String], max_guesses : Option<u64>, rng : &mut R) -> Option<Game> {
+        let rules = Ruleset::from_words(words.to_vec())?;
+        return Game::new_with_ruleset(&Ruleset { max_guesses, ..rules }, rng);
+    }
+
+    // only the words are allowed as guesses from now on, and the secret must be one of them;
+    // false, leaving the game unchanged, when it is not or a word does not fit the game
+    pub fn set_dictionary(&mut self, mut words: Vec<String>) -> bool {
+        words.sort();
+        words.dedup();
+        let rules = Ruleset { words: Some(words), ..self.rules.clone() };
+        if rules.validate().is_err() || !rules.is_valid(&self.answer) {
+            return false;
+        }
+        self.rules = rules;
+        return true;
+    }
+
+    pub fn get_dictionary(&self) -> Option<&[String]> {
+        return self.rules.words.as_deref();
+    }
+
+    pub fn get_ruleset(&self) -> &Ruleset {
+        return &self.rules;
     }
 
     pub fn get_answer(&self) -> &str {
@@ -27,24 +104,451 @@ impl Game {
     }
 
     pub fn get_answer_length(&self) -> usize {
-        self.answer.len()
+        return self.rules.length;
+    }
+
+    pub fn get_alphabet(&self) -> &[char] {
+        return &self.rules.alphabet;
+    }
+
+    pub fn get_repeats_allowed(&self) -> bool {
+        return self.rules.repeats;
+    }
+
+    pub fn get_max_guesses(&self) -> Option<u64> {
+        return self.rules.max_guesses;
+    }
+
+    pub fn get_feedback_kind(&self) -> FeedbackKind {
+        return self.rules.feedback_kind;
+    }
+
+    // what the guesses are told from now on, bulls and cows by default
+    pub fn set_feedback_kind(&mut self, feedback_kind: FeedbackKind) {
+        self.rules.feedback_kind = feedback_kind;
+    }
+
+    // the mark of every symbol of a guess this game gave the feedback to, None unless the feedback is positional
+    pub fn get_marks(&self, feedback: (usize, usize)) -> Option<Vec<Mark>> {
+        return self.rules.feedback_kind.get_marks(feedback, self.get_answer_length());
+    }
+
+    pub fn get_state(&self) -> GameState {
+        return self.state;
+    }
+
+    pub fn get_history(&self) -> &[(String, (usize, usize))] {
+        return &self.history;
+    }
+
+    pub fn get_hints(&self) -> &[Hint] {
+        return &self.hints;
+    }
+
+    pub fn get_hint_penalty(&self) -> u64 {
+        return self.hints.iter().map(|hint| hint.get_kind().cost()).sum();
+    }
+
+    // time from the start of the game until it was over (or until now)
+    pub fn get_elapsed(&self) -> Duration {
+        return self.finished_at.unwrap_or_else(Instant::now).duration_since(self.started_at);
     }
 
+    // information needed to find the secret relative to the classic game, 1.0 for 4 digits without repeats,
+    // divided by the information a guess can get relative to bulls and cows: about the guesses needed
+    pub fn get_difficulty(&self) -> f64 {
+        let information: f64 = (self.rules.get_numbers_count() as f64).log2() / CLASSIC_NUMBERS_COUNT.log2();
+        // at least a win and something else to tell apart
+        let feedback_bits = |kind: FeedbackKind| -> f64 { (kind.get_feedback_count(self.rules.length).max(2) as f64).log2() };
+        return information * feedback_bits(FeedbackKind::BullsAndCows) / feedback_bits(self.rules.feedback_kind);
+    }
+
+    // 0 unless the game was won
+    pub fn get_score(&self) -> u64 {
+        if self.state != GameState::Won {
+            return 0;
+        }
+        return compute_score(self.guess_count, self.get_elapsed(), self.get_hint_penalty(), self.get_difficulty());
+    }
+
+    // all secrets the player cannot rule out yet, given the feedback and the hints received
+    pub fn get_consistent_numbers(&self) -> Vec<String> {
+        let mut numbers = self.rules.generate_numbers();
+        numbers.retain(|number| {
+            self.history.iter().all(|(guess, feedback)| self.rules.feedback_kind.get_feedback(guess, number) == Some(*feedback))
+                && self.hints.iter().all(|hint| hint.is_consistent_with(number))
+        });
+        return numbers;
+    }
+
+    // None once the game is over or when the hint would reveal nothing new, such hints are not charged
+    pub fn request_hint(&mut self, kind: HintKind) -> Option<Hint> {
+        if self.is_over() {
+            return None;
+        }
+        let hint = hints::compute_hint(kind, &self.get_consistent_numbers(), &self.answer, &self.rules, &self.hints)?;
+        self.hints.push(hint.clone());
+        return Some(hint);
+    }
+
+    pub fn is_over(&self) -> bool {
+        return self.state != GameState::InProgress;
+    }
+
+    pub fn abandon(&mut self) {
+        if self.state == GameState::InProgress {
+            self.state = GameState::Abandoned;
+            self.finished_at = Some(Instant::now());
+        }
+    }
+
+    // returns None for invalid guesses and once the game is over, the feedback is of the game's kind;
+    // a guess is valid when it could be the secret, its length counted in symbols rather than bytes
     pub fn make_guess(&mut self, guess: &str) -> Option<(usize, usize)> {
-        if guess.len() != self.answer.len() || !guess.chars().all(char::is_alphanumeric) || guess.chars().collect::<HashSet<char>>().len() != guess.len() {
+        if self.is_over() || !self.rules.is_valid(guess) {
             return None;
         }
 
         self.guess_count += 1;
-        
-        let bulls = guess.chars().zip(self.answer.chars()).filter(|(a, b)| {
-            a == b
-        }).count();
 
-        let cows = guess.chars().filter(|&c| {
-            self.answer.contains(c)
-        }).count();
+        let (bulls, cows) = self.rules.feedback_kind.get_feedback(guess, &self.answer)?;
+        self.history.push((String::from(guess), (bulls, cows)));
+
+        if bulls == self.rules.length {
+            self.state = GameState::Won;
+        } else if self.rules.max_guesses.is_some_and(|max_guesses| self.guess_count >= max_guesses) {
+            self.state = GameState::Lost;
+        }
+        if self.is_over() {
+            self.finished_at = Some(Instant::now());
+        }
+
+        return Some((bulls, cows));
+    }
+}
 
-        return Some((bulls, cows - bulls));
+// MAX_SCORE minus the penalties for extra guesses, hints and time, scaled by the difficulty
+pub fn compute_score(guess_count : u64, elapsed : Duration, hint_penalty : u64, difficulty : f64) -> u64 {
+    let penalty = guess_count.saturating_sub(1) * GUESS_PENALTY + hint_penalty + elapsed.as_secs() / TIME_PENALTY_SECONDS;
+    return (MAX_SCORE.saturating_sub(penalty) as f64 * difficulty).round() as u64;
+}
+
+// statistics accumulated over all games played in one session
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionStats {
+    pub games_played : u64,
+    pub games_won : u64,
+    pub games_lost : u64,
+    pub games_abandoned : u64,
+    pub guesses_in_won_games : u64,
+    pub best_guess_count : Option<u64>,
+}
+
+impl SessionStats {
+    pub fn new() -> SessionStats {
+        return SessionStats::default();
+    }
+
+    // games still in progress are not counted
+    pub fn record(&mut self, game: &Game) {
+        match game.get_state() {
+            GameState::InProgress => return,
+            GameState::Won => {
+                self.games_won += 1;
+                self.guesses_in_won_games += game.get_guess_count();
+                if self.best_guess_count.is_none_or(|best| game.get_guess_count() < best) {
+                    self.best_guess_count = Some(game.get_guess_count());
+                }
+            }
+            GameState::Lost => self.games_lost += 1,
+            GameState::Abandoned => self.games_abandoned += 1,
+        }
+        self.games_played += 1;
+    }
+
+    pub fn get_average_guesses_to_win(&self) -> Option<f64> {
+        if self.games_won == 0 {
+            return None;
+        }
+        return Some(self.guesses_in_won_games as f64 / self.games_won as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Game, GameState, SessionStats};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn game_with_answer(answer: &str, max_guesses: Option<u64>) -> Game {
+        let digits: Vec<char> = "0123456789".chars().collect();
+        return Game::new_with_answer(answer, &digits, false, max_guesses).unwrap();
+    }
+
+    #[test]
+    fn test_game_new() {
+        let g = Game::new(4);
+        assert_eq!(g.get_answer_length(), 4);
+        assert_eq!(g.get_guess_count(), 0);
+        assert_eq!(g.get_state(), GameState::InProgress);
+        assert_eq!(g.get_max_guesses(), None);
+        let g = Game::new_with_guess_limit(3, Some(7));
+        assert_eq!(g.get_answer_length(), 3);
+        assert_eq!(g.get_max_guesses(), Some(7));
+    }
+    #[test]
+    fn test_game_new_with_options() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let alphabet: Vec<char> = "abcdef".chars().collect();
+        let g = Game::new_with_options(4, &alphabet, false, None, &mut rng).unwrap();
+        assert_eq!(g.get_answer_length(), 4);
+        assert!(g.get_answer().chars().all(|c| alphabet.contains(&c)));
+        assert_eq!(g.get_alphabet(), &alphabet[..]);
+        assert!(!g.get_repeats_allowed());
+        let g = Game::new_with_options(8, &alphabet, true, None, &mut rng).unwrap();
+        assert_eq!(g.get_answer_length(), 8);
+        assert!(Game::new_with_options(7, &alphabet, false, None, &mut rng).is_none());
+        assert!(Game::new_with_options(0, &alphabet, false, None, &mut rng).is_none());
+        assert!(Game::new_with_options(2, &['a', 'a'], true, None, &mut rng).is_none());
+
+        let g0 = Game::new_with_options(4, &alphabet, false, None, &mut StdRng::seed_from_u64(5)).unwrap();
+        let g1 = Game::new_with_options(4, &alphabet, false, None, &mut StdRng::seed_from_u64(5)).unwrap();
+        assert_eq!(g0.get_answer(), g1.get_answer());
+    }
+    #[test]
+    fn test_game_new_with_answer() {
+        let alphabet: Vec<char> = "abcdef".chars().collect();
+        assert!(Game::new_with_answer("abcd", &alphabet, false, None).is_some());
+        assert!(Game::new_with_answer("abca", &alphabet, false, None).is_none());
+        assert!(Game::new_with_answer("abca", &alphabet, true, None).is_some());
+        assert!(Game::new_with_answer("abcz", &alphabet, true, None).is_none());
+        assert!(Game::new_with_answer("", &alphabet, true, None).is_none());
+    }
+    #[test]
+    fn test_game_with_repeats() {
+        let alphabet: Vec<char> = "abcdef".chars().collect();
+        let mut g = Game::new_with_answer("aabc", &alphabet, true, None).unwrap();
+        assert_eq!(g.make_guess("aaaa"), Some((2, 0)));
+        assert_eq!(g.make_guess("bcaa"), Some((0, 4)));
+        assert_eq!(g.make_guess("aabc"), Some((4, 0)));
+        assert_eq!(g.get_state(), GameState::Won);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_game_unicode_guesses() {
+        // symbols are counted, not bytes
+        let digits: Vec<char> = "٠١٢٣٤٥٦٧٨٩".chars().collect();
+        let mut g = Game::new_with_answer("١٢٣٤", &digits, false, None).unwrap();
+        assert_eq!(g.get_answer_length(), 4);
+        assert_eq!(g.make_guess("1234"), None);
+        assert_eq!(g.make_guess("١٢"), None);
+        assert_eq!(g.make_guess("٤٣٢١"), Some((0, 4)));
+        assert_eq!(g.make_guess("١٢٣٤"), Some((4, 0)));
+        assert_eq!(g.get_state(), GameState::Won);
+
+        let letters: Vec<char> = "éèêa".chars().collect();
+        let mut g = Game::new_with_answer("éèa", &letters, false, None).unwrap();
+        assert_eq!(g.make_guess("eèa"), None);
+        assert_eq!(g.make_guess("éé"), None);
+        assert_eq!(g.make_guess("aéè"), Some((0, 3)));
+
+        // only the symbols of the game
+        let mut g = game_with_answer("1234", None);
+        for guess in ["abcd", "١٢٣٤", "12 4", "123", "12345", ""] {
+            assert_eq!(g.make_guess(guess), None, "{}", guess);
+        }
+        assert_eq!(g.get_guess_count(), 0);
+    }
+    #[test]
+    fn test_game_win() {
+        let mut g = game_with_answer("1234", None);
+        assert_eq!(g.make_guess("1243"), Some((2, 2)));
+        assert_eq!(g.make_guess("1123"), None);
+        assert_eq!(g.get_guess_count(), 1);
+        assert_eq!(g.make_guess("1234"), Some((4, 0)));
+        assert_eq!(g.get_state(), GameState::Won);
+        assert!(g.is_over());
+        assert_eq!(g.make_guess("1234"), None);
+        assert_eq!(g.get_guess_count(), 2);
+    }
+    #[test]
+    fn test_game_reduced_feedback() {
+        use crate::algos::feedback::FeedbackKind;
+        let mut g = game_with_answer("1234", None);
+        g.set_feedback_kind(FeedbackKind::Total);
+        assert_eq!(g.make_guess("4321"), Some((0, 4)));
+        assert_eq!(g.make_guess("1243"), Some((0, 4)));
+        // both orders of 1243 and 1234 are left
+        assert!(g.get_consistent_numbers().contains(&String::from("1234")));
+        assert!(g.get_consistent_numbers().contains(&String::from("2134")));
+        g.set_feedback_kind(FeedbackKind::AnyBull);
+        assert_eq!(g.make_guess("1567"), Some((1, 0)));
+        assert_eq!(g.make_guess("1234"), Some((4, 0)));
+        assert_eq!(g.get_state(), GameState::Won);
+    }
+    #[test]
+    fn test_game_positional_feedback() {
+        use crate::algos::feedback::{FeedbackKind, Mark};
+        let mut g = game_with_answer("1234", None);
+        g.set_feedback_kind(FeedbackKind::Positional);
+        let feedback = g.make_guess("1562").unwrap();
+        assert_eq!(feedback.0, 1);
+        assert_eq!(g.get_marks(feedback), Some(vec![Mark::Bull, Mark::Absent, Mark::Absent, Mark::Cow]));
+        assert_eq!(FeedbackKind::Positional.format(feedback, 4), "B--C");
+        // only the numbers with 1 first, 2 elsewhere than last and no 5 or 6 are left
+        let consistent = g.get_consistent_numbers();
+        assert!(consistent.contains(&String::from("1234")) && !consistent.contains(&String::from("1342")));
+        assert!(consistent.iter().all(|number| number.starts_with('1') && number.contains('2') && !number.ends_with('2')));
+        assert_eq!(g.make_guess("1234"), Some((4, 0)));
+        assert_eq!(g.get_state(), GameState::Won);
+
+        let g = game_with_answer("1234", None);
+        assert_eq!(g.get_marks((1, 0)), None);
+    }
+    #[test]
+    fn test_game_with_words() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let words: Vec<String> = ["farm", "fork", "cows", "barn"].iter().map(|word| String::from(*word)).collect();
+        let mut g = Game::new_with_words(&words, None, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert!(words.contains(&String::from(g.get_answer())));
+        assert_eq!(g.get_dictionary().unwrap().len(), 4);
+        assert_eq!(g.get_consistent_numbers().len(), 4);
+        // letters of the words but not a word
+        assert_eq!(g.make_guess("form"), None);
+        assert_eq!(g.get_guess_count(), 0);
+        let guess: &str = if g.get_answer() == "farm" { "fork" } else { "farm" };
+        assert!(g.make_guess(guess).is_some());
+        let answer: String = String::from(g.get_answer());
+        assert_eq!(g.make_guess(&answer), Some((4, 0)));
+
+        let mut g = Game::new_with_answer("cows", &"abcfkmnorsw".chars().collect::<Vec<char>>(), false, None).unwrap();
+        assert!(!g.set_dictionary(vec![String::from("farm")]));
+        assert!(!g.set_dictionary(vec![String::from("cows"), String::from("moon")]));
+        assert!(g.get_dictionary().is_none());
+        assert!(Game::new_with_words(&[], None, &mut StdRng::seed_from_u64(1)).is_none());
+    }
+    #[test]
+    fn test_game_guess_limit() {
+        let mut g = game_with_answer("1234", Some(2));
+        assert_eq!(g.make_guess("5678"), Some((0, 0)));
+        assert_eq!(g.get_state(), GameState::InProgress);
+        assert_eq!(g.make_guess("5679"), Some((0, 0)));
+        assert_eq!(g.get_state(), GameState::Lost);
+        assert_eq!(g.make_guess("1234"), None);
+
+        let mut g = game_with_answer("1234", Some(1));
+        assert_eq!(g.make_guess("1234"), Some((4, 0)));
+        assert_eq!(g.get_state(), GameState::Won);
+    }
+    #[test]
+    fn test_game_abandon() {
+        let mut g = game_with_answer("1234", None);
+        g.abandon();
+        assert_eq!(g.get_state(), GameState::Abandoned);
+        assert_eq!(g.make_guess("1234"), None);
+
+        let mut g = game_with_answer("1234", None);
+        g.make_guess("1234");
+        g.abandon();
+        assert_eq!(g.get_state(), GameState::Won);
+    }
+    #[test]
+    fn test_game_history_and_score() {
+        let mut g = game_with_answer("1234", None);
+        assert_eq!(g.make_guess("5678"), Some((0, 0)));
+        assert_eq!(g.get_score(), 0);
+        assert_eq!(g.make_guess("1243"), Some((2, 2)));
+        assert_eq!(g.make_guess("1234"), Some((4, 0)));
+        assert_eq!(g.get_history().len(), 3);
+        assert_eq!(g.get_history()[1], (String::from("1243"), (2, 2)));
+        assert_eq!(g.get_score(), super::MAX_SCORE - 2 * super::GUESS_PENALTY);
+
+        let mut g = game_with_answer("1234", Some(1));
+        g.make_guess("5678");
+        assert_eq!(g.get_score(), 0);
+    }
+    #[test]
+    fn test_compute_score() {
+        use super::compute_score;
+        use std::time::Duration;
+        assert_eq!(compute_score(1, Duration::from_secs(0), 0, 1.0), 1000);
+        assert_eq!(compute_score(5, Duration::from_secs(65), 25, 1.0), 1000 - 400 - 25 - 6);
+        assert_eq!(compute_score(5, Duration::from_secs(0), 0, 1.5), 900);
+        assert_eq!(compute_score(20, Duration::from_secs(0), 0, 1.0), 0);
+    }
+    #[test]
+    fn test_game_difficulty_and_elapsed() {
+        let g = game_with_answer("1234", None);
+        assert_eq!(g.get_difficulty(), 1.0);
+        let g = game_with_answer("123", None);
+        assert!(g.get_difficulty() < 1.0);
+        let alphabet: Vec<char> = "0123456789abcdef".chars().collect();
+        let g = Game::new_with_answer("12345", &alphabet, true, None).unwrap();
+        assert!(g.get_difficulty() > 1.0);
+        // the less the feedback tells, the harder
+        use crate::algos::feedback::FeedbackKind;
+        let difficulty = |kind: FeedbackKind| -> f64 {
+            let mut g = game_with_answer("1234", None);
+            g.set_feedback_kind(kind);
+            return g.get_difficulty();
+        };
+        assert_eq!(difficulty(FeedbackKind::BullsAndCows), 1.0);
+        assert!(1.0 < difficulty(FeedbackKind::Total) && difficulty(FeedbackKind::Total) < difficulty(FeedbackKind::BullsOnly));
+        assert!(difficulty(FeedbackKind::BullsOnly) < difficulty(FeedbackKind::AnyBull));
+
+        let mut g = game_with_answer("1234", None);
+        g.make_guess("1234");
+        let elapsed = g.get_elapsed();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert_eq!(g.get_elapsed(), elapsed);
+    }
+    #[test]
+    fn test_game_hints() {
+        use crate::hints::{Hint, HintKind};
+        let mut g = game_with_answer("1234", None);
+        assert_eq!(g.request_hint(HintKind::RemainingCount), Some(Hint::RemainingCount(5040)));
+        g.make_guess("5678");
+        assert_eq!(g.request_hint(HintKind::RemainingCount), Some(Hint::RemainingCount(360)));
+        assert_eq!(g.request_hint(HintKind::PresentSymbol), Some(Hint::PresentSymbol('1')));
+        assert_eq!(g.get_consistent_numbers().len(), 360 * 4 / 6);
+        assert_eq!(g.request_hint(HintKind::ConfirmedPosition), Some(Hint::ConfirmedPosition(0, '1')));
+        assert!(g.get_consistent_numbers().iter().all(|number| number.starts_with('1')));
+        let suggestion = g.request_hint(HintKind::SuggestedGuess);
+        assert!(matches!(suggestion, Some(Hint::SuggestedGuess(_))));
+        assert_eq!(g.get_hints().len(), 5);
+        assert_eq!(g.get_hint_penalty(), 25 + 25 + 75 + 150 + 250);
+
+        g.make_guess("1234");
+        assert_eq!(g.get_score(), super::MAX_SCORE - super::GUESS_PENALTY - g.get_hint_penalty());
+        assert_eq!(g.request_hint(HintKind::RemainingCount), None);
+    }
+    #[test]
+    fn test_session_stats() {
+        let mut stats = SessionStats::new();
+        assert_eq!(stats.get_average_guesses_to_win(), None);
+
+        let mut g = game_with_answer("1234", None);
+        stats.record(&g);
+        assert_eq!(stats.games_played, 0);
+        g.make_guess("5678");
+        g.make_guess("1234");
+        stats.record(&g);
+
+        let mut g = game_with_answer("1234", None);
+        g.make_guess("1234");
+        stats.record(&g);
+
+        let mut g = game_with_answer("1234", Some(1));
+        g.make_guess("5678");
+        stats.record(&g);
+
+        let mut g = game_with_answer("1234", None);
+        g.abandon();
+        stats.record(&g);
+
+        assert_eq!(stats.games_played, 4);
+        assert_eq!(stats.games_won, 2);
+        assert_eq!(stats.games_lost, 1);
+        assert_eq!(stats.games_abandoned, 1);
+        assert_eq!(stats.best_guess_count, Some(1));
+        assert_eq!(stats.get_average_guesses_to_win(), Some(1.5));
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+use crate::algos::depth_one_entropy_algo::DepthOneEntropyAlgo;
+use crate::algos::knowledge::Knowledge;
+use crate::ruleset::Ruleset;
+
+// Hints for human players, from the cheapest to the most revealing one.
+// Every hint given is logged by the game and its cost is taken off the score.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HintKind {
+    RemainingCount,
+    PresentSymbol,
+    ConfirmedPosition,
+    SuggestedGuess,
+}
+
+pub const HINT_KINDS: [HintKind; 4] = [HintKind::RemainingCount, HintKind::PresentSymbol, HintKind::ConfirmedPosition, HintKind::SuggestedGuess];
+
+impl HintKind {
+    // in score points, a guess costs 100
+    pub fn cost(&self) -> u64 {
+        match self {
+            HintKind::RemainingCount => return 25,
+            HintKind::PresentSymbol => return 75,
+            HintKind::ConfirmedPosition => return 150,
+            HintKind::SuggestedGuess => return 250,
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            HintKind::RemainingCount => return "how many secrets are still possible",
+            HintKind::PresentSymbol => return "a symbol that is in the secret",
+            HintKind::ConfirmedPosition => return "a symbol at its right position",
+            HintKind::SuggestedGuess => return "a good next guess",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hint {
+    RemainingCount(usize),
+    PresentSymbol(char),
+    // 0-based position
+    ConfirmedPosition(usize, char),
+    SuggestedGuess(String),
+}
+
+impl Hint {
+    pub fn get_kind(&self) -> HintKind {
+        match self {
+            Hint::RemainingCount(_) => return HintKind::RemainingCount,
+            Hint::PresentSymbol(_) => return HintKind::PresentSymbol,
+            Hint::ConfirmedPosition(_, _) => return HintKind::ConfirmedPosition,
+            Hint::SuggestedGuess(_) => return HintKind::SuggestedGuess,
+        }
+    }
+
+    // whether a possible secret agrees with what the hint revealed
+    pub fn is_consistent_with(&self, number: &str) -> bool {
+        match self {
+            Hint::PresentSymbol(symbol) => return number.contains(*symbol),
+            Hint::ConfirmedPosition(position, symbol) => return number.chars().nth(*position) == Some(*symbol),
+            Hint::RemainingCount(_) | Hint::SuggestedGuess(_) => return true,
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::RemainingCount(count) => write!(f, "{} possible secrets remain", count),
+            Hint::PresentSymbol(symbol) => write!(f, "{} is in the secret", symbol),
+            Hint::ConfirmedPosition(position, symbol) => write!(f, "{} is at position {}", symbol, position + 1),
+            Hint::SuggestedGuess(guess) => write!(f, "try {}", guess),
+        }
+    }
+}
+
+// `numbers` are the secrets of the rules consistent with the guesses and hints so far, `given` the hints
+// already given, the suggested guess is the best against a keeper of the feedback kind of the rules.
+// Symbols and positions are only revealed when the player cannot already know them,
+// None means the hint would tell nothing new.
+pub fn compute_hint(kind: HintKind, numbers: &[String], answer: &str, rules: &Ruleset, given: &[Hint]) -> Option<Hint> {
+    let knowledge = Knowledge::from_candidates(numbers, rules)?;
+    match kind {
+        HintKind::RemainingCount => return Some(Hint::RemainingCount(numbers.len())),
+        HintKind::PresentSymbol => {
+            return answer.chars()
+                .find(|symbol| !knowledge.present.contains(symbol) && !given.contains(&Hint::PresentSymbol(*symbol)))
+                .map(Hint::PresentSymbol);
+        }
+        HintKind::ConfirmedPosition => {
+            let confirmed = knowledge.get_confirmed_positions();
+            return answer.chars().enumerate()
+                .find(|(position, symbol)| !confirmed.contains(&(*position, *symbol)))
+                .map(|(position, symbol)| Hint::ConfirmedPosition(position, symbol));
+        }
+        HintKind::SuggestedGuess => {
+            let solver = DepthOneEntropyAlgo::new(numbers.to_vec(), rules)?;
+            let (guess, _) = solver.suggest_guess()?;
+            return Some(Hint::SuggestedGuess(guess));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::feedback::FeedbackKind;
+    use crate::ruleset::Ruleset;
+    use super::{compute_hint, Hint, HintKind};
+
+    #[test]
+    fn test_hint_is_consistent_with() {
+        assert!(Hint::PresentSymbol('3').is_consistent_with("1234"));
+        assert!(!Hint::PresentSymbol('5').is_consistent_with("1234"));
+        assert!(Hint::ConfirmedPosition(1, '2').is_consistent_with("1234"));
+        assert!(!Hint::ConfirmedPosition(1, '3').is_consistent_with("1234"));
+        assert!(Hint::RemainingCount(3).is_consistent_with("1234"));
+        assert_eq!(Hint::ConfirmedPosition(1, '2').to_string(), "2 is at position 2");
+    }
+    #[test]
+    fn test_compute_hint() {
+        let numbers = vec![String::from("1234"), String::from("1243"), String::from("1534")];
+        assert_eq!(compute_hint(HintKind::RemainingCount, &numbers, "1243", &Ruleset::classic(), &[]), Some(Hint::RemainingCount(3)));
+        // 1 and 3 are in every candidate already
+        assert_eq!(compute_hint(HintKind::PresentSymbol, &numbers, "1243", &Ruleset::classic(), &[]), Some(Hint::PresentSymbol('2')));
+        assert_eq!(compute_hint(HintKind::PresentSymbol, &numbers, "1243", &Ruleset::classic(), &[Hint::PresentSymbol('2')]), None);
+        // position 1 is confirmed already
+        assert_eq!(compute_hint(HintKind::ConfirmedPosition, &numbers, "1243", &Ruleset::classic(), &[]), Some(Hint::ConfirmedPosition(1, '2')));
+        assert!(matches!(compute_hint(HintKind::SuggestedGuess, &numbers, "1243", &Ruleset::classic(), &[]), Some(Hint::SuggestedGuess(_))));
+        assert_eq!(compute_hint(HintKind::RemainingCount, &[], "1243", &Ruleset::classic(), &[]), None);
+    }
+    #[test]
+    fn test_suggested_guess_follows_the_feedback_kind() {
+        use crate::algos::algos_utils::generate_init_values_for_numbers;
+        // the numbers left after "012" got 0b 2c split differently by each kind of feedback
+        let numbers: Vec<String> = generate_init_values_for_numbers(3).into_iter().filter(|number| FeedbackKind::BullsAndCows.get_feedback("012", number) == Some((0, 2))).collect();
+        let rules = Ruleset::new(3, &Ruleset::classic().alphabet, false);
+        let suggest = |feedback_kind: FeedbackKind| compute_hint(HintKind::SuggestedGuess, &numbers, &numbers[0], &Ruleset { feedback_kind, ..rules.clone() }, &[]);
+        assert_eq!(suggest(FeedbackKind::BullsAndCows), Some(Hint::SuggestedGuess(String::from("123"))));
+        assert_eq!(suggest(FeedbackKind::Total), Some(Hint::SuggestedGuess(String::from("103"))));
+        assert_eq!(suggest(FeedbackKind::Positional), Some(Hint::SuggestedGuess(String::from("140"))));
+    }
+    #[test]
+    fn test_hint_kind_costs_are_graded() {
+        use super::HINT_KINDS;
+        for pair in HINT_KINDS.windows(2) {
+            assert!(pair[0].cost() < pair[1].cost());
+        }
+    }
+}
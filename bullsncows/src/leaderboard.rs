@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::game::{Game, GameState};
+
+// Results of finished games, stored one per line in a tab separated file:
+// player, variant, won, guesses, hints, seconds, score, unix timestamp
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub player: String,
+    pub variant: String,
+    pub won: bool,
+    pub guesses: u64,
+    pub hints: u64,
+    pub seconds: u64,
+    pub score: u64,
+    pub timestamp: u64,
+}
+
+// tabs and newlines would break the file format
+fn sanitize(field: &str) -> String {
+    return field.replace(['\t', '\n', '\r'], " ");
+}
+
+impl GameRecord {
+    // None for games that are not won or lost yet (or were abandoned)
+    pub fn from_game(player: &str, game: &Game) -> Option<GameRecord> {
+        let won = match game.get_state() {
+            GameState::Won => true,
+            GameState::Lost => false,
+            GameState::InProgress | GameState::Abandoned => return None,
+        };
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        return Some(GameRecord {
+            player: String::from(player),
+            variant: game.get_ruleset().get_key(),
+            won,
+            guesses: game.get_guess_count(),
+            hints: game.get_hints().len() as u64,
+            seconds: game.get_elapsed().as_secs(),
+            score: game.get_score(),
+            timestamp,
+        });
+    }
+
+    pub fn to_line(&self) -> String {
+        return format!("{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}", sanitize(&self.player), sanitize(&self.variant), if self.won { 1 } else { 0 },
+            self.guesses, self.hints, self.seconds, self.score, self.timestamp);
+    }
+
+    pub fn from_line(line: &str) -> Option<GameRecord> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 8 {
+            return None;
+        }
+        return Some(GameRecord {
+            player: String::from(fields[0]),
+            variant: String::from(fields[1]),
+            won: match fields[2] { "1" => true, "0" => false, _ => return None },
+            guesses: fields[3].parse().ok()?,
+            hints: fields[4].parse().ok()?,
+            seconds: fields[5].parse().ok()?,
+            score: fields[6].parse().ok()?,
+            timestamp: fields[7].parse().ok()?,
+        });
+    }
+}
+
+pub struct ScoreStore {
+    path: PathBuf,
+}
+
+impl ScoreStore {
+    pub fn new(path: &Path) -> ScoreStore {
+        return ScoreStore { path: path.to_path_buf() };
+    }
+
+    // $BULLSNCOWS_SCORES, otherwise ~/.bullsncows_scores.tsv
+    pub fn default_path() -> PathBuf {
+        if let Ok(path) = std::env::var("BULLSNCOWS_SCORES") {
+            return PathBuf::from(path);
+        }
+        let home = std::env::var("HOME").map(PathBuf::from).unwrap_or_default();
+        return home.join(".bullsncows_scores.tsv");
+    }
+
+    pub fn get_path(&self) -> &Path {
+        return &self.path;
+    }
+
+    // a missing file is an empty store, malformed lines are skipped
+    pub fn load(&self) -> io::Result<Vec<GameRecord>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        return Ok(text.lines().filter_map(GameRecord::from_line).collect());
+    }
+
+    pub fn append(&self, record: &GameRecord) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        return writeln!(file, "{}", record.to_line());
+    }
+}
+
+// best won game of the player in every variant they played
+pub fn personal_bests(records: &[GameRecord], player: &str) -> Vec<GameRecord> {
+    let mut bests: BTreeMap<&str, &GameRecord> = BTreeMap::new();
+    for record in records.iter().filter(|record| record.won && record.player == player) {
+        let best = bests.entry(record.variant.as_str()).or_insert(record);
+        if record.score > best.score || (record.score == best.score && record.guesses < best.guesses) {
+            *best = record;
+        }
+    }
+    return bests.into_values().cloned().collect();
+}
+
+// best score of every player in the variant, highest first
+pub fn leaderboard(records: &[GameRecord], variant: &str, limit: usize) -> Vec<GameRecord> {
+    let mut best_per_player: BTreeMap<&str, &GameRecord> = BTreeMap::new();
+    for record in records.iter().filter(|record| record.won && record.variant == variant) {
+        let best = best_per_player.entry(record.player.as_str()).or_insert(record);
+        if record.score > best.score {
+            *best = record;
+        }
+    }
+    let mut board: Vec<GameRecord> = best_per_player.into_values().cloned().collect();
+    board.sort_by(|a, b| b.score.cmp(&a.score).then(a.guesses.cmp(&b.guesses)).then(a.timestamp.cmp(&b.timestamp)));
+    board.truncate(limit);
+    return board;
+}
+
+// number of won games per guess count, optionally for one player and/or one variant
+pub fn guess_count_distribution(records: &[GameRecord], player: Option<&str>, variant: Option<&str>) -> BTreeMap<u64, usize> {
+    let mut distribution: BTreeMap<u64, usize> = BTreeMap::new();
+    for record in records.iter() {
+        if !record.won || player.is_some_and(|player| record.player != player) || variant.is_some_and(|variant| record.variant != variant) {
+            continue;
+        }
+        *distribution.entry(record.guesses).or_insert(0) += 1;
+    }
+    return distribution;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{guess_count_distribution, leaderboard, personal_bests, GameRecord, ScoreStore};
+
+    fn record(player: &str, variant: &str, won: bool, guesses: u64, score: u64) -> GameRecord {
+        return GameRecord { player: String::from(player), variant: String::from(variant), won, guesses, hints: 0, seconds: 30, score, timestamp: 0 };
+    }
+
+    #[test]
+    fn test_game_record_lines() {
+        let original = record("ann", "4/0123456789", true, 5, 600);
+        assert_eq!(GameRecord::from_line(&original.to_line()), Some(original));
+        let tabbed = record("a\tb", "4/0123456789", false, 7, 0);
+        assert_eq!(GameRecord::from_line(&tabbed.to_line()).unwrap().player, "a b");
+        assert_eq!(GameRecord::from_line("ann\t4/0123456789\t1\t5"), None);
+        assert_eq!(GameRecord::from_line("ann\t4/0123456789\tx\t5\t0\t0\t0\t0"), None);
+    }
+    #[test]
+    fn test_game_record_from_game() {
+        use crate::game::Game;
+        let digits: Vec<char> = "0123456789".chars().collect();
+        let mut g = Game::new_with_answer("1234", &digits, false, None).unwrap();
+        assert_eq!(GameRecord::from_game("ann", &g), None);
+        g.make_guess("1243");
+        g.make_guess("1234");
+        let r = GameRecord::from_game("ann", &g).unwrap();
+        assert_eq!(r.variant, "4/0123456789");
+        assert!(r.won);
+        assert_eq!(r.guesses, 2);
+        assert_eq!(r.score, g.get_score());
+
+        let mut g = Game::new_with_answer("1234", &digits, false, None).unwrap();
+        g.abandon();
+        assert_eq!(GameRecord::from_game("ann", &g), None);
+
+        // a harder kind of feedback is another leaderboard
+        use crate::algos::feedback::FeedbackKind;
+        use crate::ruleset::Ruleset;
+        let rules = Ruleset { feedback_kind: FeedbackKind::AnyBull, ..Ruleset::classic() };
+        let mut g = Game::new_with_ruleset_and_answer(&rules, "1234").unwrap();
+        g.make_guess("1234");
+        assert_eq!(GameRecord::from_game("ann", &g).unwrap().variant, "4/0123456789/any-bull");
+    }
+    #[test]
+    fn test_score_store() {
+        let path = std::env::temp_dir().join(format!("bullsncows_store_{}.tsv", std::process::id()));
+        let store = ScoreStore::new(&path);
+        assert_eq!(store.load().unwrap().len(), 0);
+        store.append(&record("ann", "4/0123456789", true, 5, 600)).unwrap();
+        store.append(&record("bob", "4/0123456789", false, 10, 0)).unwrap();
+        let records = store.load().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].player, "bob");
+        std::fs::remove_file(&path).unwrap();
+    }
+    #[test]
+    fn test_queries() {
+        let records = vec![
+            record("ann", "4/0123456789", true, 5, 600),
+            record("ann", "4/0123456789", true, 4, 700),
+            record("ann", "3/0123456789", true, 4, 500),
+            record("ann", "4/0123456789", false, 10, 0),
+            record("bob", "4/0123456789", true, 6, 650),
+            record("cid", "4/0123456789", true, 5, 800),
+        ];
+        let bests = personal_bests(&records, "ann");
+        assert_eq!(bests.len(), 2);
+        assert_eq!(bests[0].variant, "3/0123456789");
+        assert_eq!(bests[1].score, 700);
+
+        let board = leaderboard(&records, "4/0123456789", 10);
+        let players: Vec<&str> = board.iter().map(|r| r.player.as_str()).collect();
+        assert_eq!(players, vec!["cid", "ann", "bob"]);
+        assert_eq!(leaderboard(&records, "4/0123456789", 1).len(), 1);
+
+        let distribution = guess_count_distribution(&records, None, Some("4/0123456789"));
+        assert_eq!(distribution.get(&4), Some(&1));
+        assert_eq!(distribution.get(&5), Some(&2));
+        assert_eq!(distribution.get(&10), None);
+        let distribution = guess_count_distribution(&records, Some("ann"), None);
+        assert_eq!(distribution.values().sum::<usize>(), 3);
+    }
+}
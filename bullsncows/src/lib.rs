@@ -0,0 +1,13 @@
+#![allow(clippy::needless_return)]
+
+pub mod algos;
+pub mod clues;
+pub mod dictionary;
+pub mod game;
+pub mod hints;
+pub mod leaderboard;
+pub mod mastermind;
+pub mod multi;
+pub mod ruleset;
+pub mod transcript;
+pub mod wordle;
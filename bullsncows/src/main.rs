@@ -1,26 +1,166 @@
-mod game;
+#![allow(clippy::needless_return)]
 
-fn main() {
-    let mut g = game::Game::new(4); 
-    println!("Guess the number: {}", g.get_answer());
-    
-    for _ in 1..100 {
-        let mut user_guess = String::new();
-        std::io::stdin().read_line(&mut user_guess).expect("Error while reading user input");
-        user_guess = String::from(user_guess.trim());
+mod commands;
+
+use std::path::PathBuf;
+use clap::{builder::FalseyValueParser, Parser, Subcommand};
+use bullsncows::leaderboard::ScoreStore;
+use bullsncows::multi;
+use commands::{BudgetArgs, OutputFormat, SolverArgs, SolverListArgs, VariantArgs};
+
+/// Bulls and cows: play the game, let the computer solve it or get help in your own games
+#[derive(Parser)]
+#[command(name = "bullsncows", version)]
+struct Cli {
+    /// Output format of solve, bench, engine, optimal, static and replay
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    format: OutputFormat,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
 
-        match g.make_guess(&user_guess) {
-            None => {
-                println!("Invalid input!");
-                continue;
-            }
-            Some((bulls, cows)) => {
-                println!("Bulls: {}, cows: {}", bulls, cows);
+#[derive(Subcommand)]
+enum Command {
+    /// Guess a secret number chosen by the computer (default)
+    Play {
+        #[command(flatten)]
+        variant: VariantArgs,
+        /// Show the secret at the start of every game, also when $BULLSNCOWS_DEBUG is set to anything but false, no, off or 0
+        #[arg(long, env = "BULLSNCOWS_DEBUG", value_parser = FalseyValueParser::new())]
+        debug: bool,
+        /// Save the results of finished games under this name
+        #[arg(long)]
+        player: Option<String>,
+        /// Score file, $BULLSNCOWS_SCORES or ~/.bullsncows_scores.tsv by default
+        #[arg(long)]
+        store: Option<PathBuf>,
+    },
+    /// Play in a full screen terminal UI showing what is known about the secret
+    Tui {
+        #[command(flatten)]
+        variant: VariantArgs,
+    },
+    /// Guess several secrets at once, every guess is scored against each of them
+    Multi {
+        #[command(flatten)]
+        variant: VariantArgs,
+        /// Number of secrets
+        #[arg(long, default_value_t = multi::DEFAULT_BOARDS as u64, value_parser = clap::value_parser!(u64).range(1..))]
+        boards: u64,
+        /// Let the solver play instead
+        #[arg(long)]
+        bot: bool,
+    },
+    /// Let a solver guess a secret and print the game transcript
+    Solve {
+        #[command(flatten)]
+        variant: VariantArgs,
+        #[command(flatten)]
+        solver: SolverArgs,
+        /// Secret to guess, random by default
+        #[arg(long)]
+        secret: Option<String>,
+    },
+    /// Get suggested guesses for a game played elsewhere, entering the feedback by hand
+    Assist {
+        #[command(flatten)]
+        variant: VariantArgs,
+        #[command(flatten)]
+        solver: SolverArgs,
+    },
+    /// Measure how many guesses solvers need on average
+    Bench {
+        #[command(flatten)]
+        variant: VariantArgs,
+        #[command(flatten)]
+        solvers: SolverListArgs,
+        /// Number of random secrets to play against
+        #[arg(long, default_value_t = 100)]
+        games: usize,
+        /// Play against every possible secret instead
+        #[arg(long)]
+        all: bool,
+    },
+    /// Drive a solver through a line based protocol on stdin/stdout
+    Engine {
+        #[command(flatten)]
+        variant: VariantArgs,
+        #[command(flatten)]
+        solver: SolverArgs,
+    },
+    /// Host games over TCP, one session per connection
+    Serve {
+        #[command(flatten)]
+        variant: VariantArgs,
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port to listen on, 0 picks a free one
+        #[arg(long, default_value_t = 4040)]
+        port: u16,
+        /// Send the secret to the players at the start of every game
+        #[arg(long)]
+        debug: bool,
+    },
+    /// Show personal bests, leaderboards and guess count statistics of saved games
+    Scores {
+        #[command(subcommand)]
+        query: commands::scores::ScoresQuery,
+        /// Score file, $BULLSNCOWS_SCORES or ~/.bullsncows_scores.tsv by default
+        #[arg(long, global = true)]
+        store: Option<PathBuf>,
+    },
+    /// Search the strategy needing the fewest guesses on average and print its decision tree
+    Optimal {
+        #[command(flatten)]
+        variant: VariantArgs,
+        /// Only guess numbers that can still be the secret
+        #[arg(long)]
+        candidates_only: bool,
+    },
+    /// Find the fewest guesses to submit all at once whose feedbacks together identify any secret
+    #[command(name = "static")]
+    Static {
+        #[command(flatten)]
+        variant: VariantArgs,
+        #[command(flatten)]
+        budget: BudgetArgs,
+    },
+    /// Check a game transcript against a secret or list the secrets consistent with it
+    Replay {
+        #[command(flatten)]
+        variant: VariantArgs,
+        /// Transcript file with lines like "9435 1b 1c"
+        file: PathBuf,
+        /// Secret to check against, overrides the "Number to guess" header
+        #[arg(long)]
+        secret: Option<String>,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    // without a command the game is played, its options and environment read as for `play`
+    let command = cli.command.unwrap_or_else(|| Cli::parse_from([env!("CARGO_BIN_NAME"), "play"]).command.unwrap());
 
-                if bulls == g.get_answer_length() && cows == 0 {
-                    println!("You won after {} guesses", g.get_guess_count());
-                }
-            }
+    match command {
+        Command::Play { variant, debug, player, store } => {
+            let store = ScoreStore::new(&store.unwrap_or_else(ScoreStore::default_path));
+            commands::play::run(&variant, debug, player.as_deref(), &store);
+        }
+        Command::Tui { variant } => commands::tui::run(&variant),
+        Command::Multi { variant, boards, bot } => commands::multi::run(&variant, boards as usize, bot),
+        Command::Solve { variant, solver, secret } => commands::solve::run(&variant, &solver, secret, cli.format),
+        Command::Assist { variant, solver } => commands::assist::run(&variant, &solver),
+        Command::Bench { variant, solvers, games, all } => commands::bench::run(&variant, &solvers, games, all, cli.format),
+        Command::Engine { variant, solver } => commands::engine::run(&variant, &solver, cli.format),
+        Command::Serve { variant, host, port, debug } => commands::serve::run(&variant, &host, port, debug),
+        Command::Scores { query, store } => {
+            let store = ScoreStore::new(&store.unwrap_or_else(ScoreStore::default_path));
+            commands::scores::run(&query, &store);
         }
+        Command::Optimal { variant, candidates_only } => commands::optimal::run(&variant, candidates_only, cli.format),
+        Command::Static { variant, budget } => commands::separating::run(&variant, &budget, cli.format),
+        Command::Replay { variant, file, secret } => commands::replay::run(&variant, &file, secret, cli.format),
     }
 }
@@ -0,0 +1,107 @@
+use crossterm::style::{Color, Stylize};
+use rand::Rng;
+use crate::game::Game;
+
+// Mastermind is bulls and cows over colors with repeats allowed: a black key peg is a bull,
+// a white key peg is a cow, and `algos_utils::get_bulls_and_cows` already scores repeated
+// colors the Mastermind way. Colors are written as letters so that every solver plays it.
+
+pub const DEFAULT_PEGS: usize = 4;
+pub const DEFAULT_COLORS: usize = 6;
+
+// letter, name and terminal color, the classic six first
+pub const COLORS: [(char, &str, Color); 8] = [
+    ('R', "red", Color::Red),
+    ('G', "green", Color::Green),
+    ('B', "blue", Color::Blue),
+    ('Y', "yellow", Color::Yellow),
+    ('O', "orange", Color::DarkYellow),
+    ('P', "purple", Color::Magenta),
+    ('W', "white", Color::White),
+    ('K', "black", Color::DarkGrey),
+];
+
+// the letters of the first `colors` colors, None beyond the colors known
+pub fn get_color_alphabet(colors: usize) -> Option<Vec<char>> {
+    if colors == 0 || colors > COLORS.len() {
+        return None;
+    }
+    return Some(COLORS[..colors].iter().map(|(letter, _, _)| *letter).collect());
+}
+
+pub fn new_game<R: Rng>(pegs: usize, colors: usize, max_guesses: Option<u64>, rng: &mut R) -> Option<Game> {
+    return Game::new_with_options(pegs, &get_color_alphabet(colors)?, true, max_guesses, rng);
+}
+
+// every peg as its letter in its color, unknown letters as they are
+pub fn render_guess(guess: &str) -> String {
+    let pegs: Vec<String> = guess.chars().map(|letter| match COLORS.iter().find(|(color_letter, _, _)| *color_letter == letter) {
+        Some((_, _, color)) => letter.with(*color).bold().to_string(),
+        None => letter.to_string(),
+    }).collect();
+    return pegs.join(" ");
+}
+
+// a black peg per bull, a white one per cow and a dot per peg that scored nothing
+pub fn render_key_pegs(feedback: (usize, usize), pegs: usize) -> String {
+    let (black, white) = feedback;
+    let mut rendered = String::new();
+    rendered.push_str(&"●".repeat(black).bold().to_string());
+    rendered.push_str(&"○".repeat(white));
+    rendered.push_str(&"·".repeat(pegs.saturating_sub(black + white)).dark_grey().to_string());
+    return rendered;
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::algos::create_solver;
+    use crate::algos::algos_utils::get_bulls_and_cows;
+    use crate::game::{Game, GameState};
+    use crate::ruleset::Ruleset;
+    use super::{get_color_alphabet, new_game, render_guess, render_key_pegs};
+
+    #[test]
+    fn test_get_color_alphabet() {
+        assert_eq!(get_color_alphabet(6), Some(vec!['R', 'G', 'B', 'Y', 'O', 'P']));
+        assert_eq!(get_color_alphabet(8).unwrap().len(), 8);
+        assert_eq!(get_color_alphabet(9), None);
+        assert_eq!(get_color_alphabet(0), None);
+    }
+    #[test]
+    fn test_mastermind_game() {
+        let mut game = new_game(4, 6, Some(10), &mut StdRng::seed_from_u64(1)).unwrap();
+        assert!(game.get_repeats_allowed());
+        assert!(game.get_answer().chars().all(|letter| "RGBYOP".contains(letter)));
+        assert!(game.make_guess("RRGG").is_some());
+        assert!(new_game(4, 9, None, &mut StdRng::seed_from_u64(1)).is_none());
+        // one black for the second R, one white for the other R and one for a G
+        assert_eq!(get_bulls_and_cows("RRGG", "GRRB"), Some((1, 2)));
+    }
+    #[test]
+    fn test_solvers_play_mastermind() {
+        let rules = Ruleset::from_preset("mastermind").unwrap();
+        assert_eq!(rules.get_numbers_count(), 1296);
+        for (name, secret) in [("entropy", "RRRR"), ("worst-case", "PYOB"), ("baseline", "GGBO")] {
+            let mut solver = create_solver(name, &rules, Some(1), None).unwrap();
+            let mut game = Game::new_with_ruleset_and_answer(&rules, secret).unwrap();
+            while !game.is_over() {
+                let guess = solver.guess().unwrap();
+                let (black, white) = game.make_guess(&guess).unwrap();
+                solver.incorporate_guess_feedback(black, white);
+            }
+            assert_eq!(game.get_state(), GameState::Won, "{} {}", name, secret);
+        }
+    }
+    #[test]
+    fn test_render() {
+        let rendered = render_key_pegs((1, 2), 4);
+        assert_eq!(rendered.matches('●').count(), 1);
+        assert_eq!(rendered.matches('○').count(), 2);
+        assert_eq!(rendered.matches('·').count(), 1);
+        let rendered = render_guess("RGBx");
+        for letter in ['R', 'G', 'B', 'x'] {
+            assert!(rendered.contains(letter));
+        }
+    }
+}
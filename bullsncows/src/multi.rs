@@ -0,0 +1,195 @@
+use rand::Rng;
+use crate::algos::feedback::FeedbackKind;
+use crate::game::{Game, GameState};
+use crate::ruleset::Ruleset;
+
+// Several secrets at once, like Quordle: every guess is scored against each secret that is not
+// found yet and the game is won once all of them are. Each secret is a `Game` of its own, a
+// board, and the guess limit counts the guesses made for all of them together.
+
+pub const DEFAULT_BOARDS: usize = 4;
+
+// the feedback of a guess on every board, None on the boards solved before it
+pub type BoardFeedbacks = Vec<Option<(usize, usize)>>;
+
+pub struct MultiGame {
+    boards : Vec<Game>,
+    guess_count : u64,
+    max_guesses : Option<u64>,
+    history : Vec<(String, BoardFeedbacks)>,
+    state : GameState,
+}
+
+impl MultiGame {
+    // None when no board can be drawn, see `Game::new_with_options`, or without boards
+    pub fn new_with_options<R: Rng>(boards: usize, length: usize, alphabet: &[char], repeats_allowed: bool, max_guesses: Option<u64>, rng: &mut R) -> Option<MultiGame> {
+        return MultiGame::new_with_ruleset(boards, &Ruleset { max_guesses, ..Ruleset::new(length, alphabet, repeats_allowed) }, rng);
+    }
+
+    // boards of the rules, whose guess limit counts for all of them
+    pub fn new_with_ruleset<R: Rng>(boards: usize, rules: &Ruleset, rng: &mut R) -> Option<MultiGame> {
+        let board_rules = Ruleset { max_guesses: None, ..rules.clone() };
+        let mut games: Vec<Game> = Vec::new();
+        for _ in 0..boards {
+            games.push(Game::new_with_ruleset(&board_rules, rng)?);
+        }
+        return MultiGame::new_with_boards(games, rules.max_guesses);
+    }
+
+    // boards with known answers, all of the same length
+    pub fn new_with_answers(answers: &[&str], alphabet: &[char], repeats_allowed: bool, max_guesses: Option<u64>) -> Option<MultiGame> {
+        let mut games: Vec<Game> = Vec::new();
+        for answer in answers.iter() {
+            games.push(Game::new_with_answer(answer, alphabet, repeats_allowed, None)?);
+        }
+        if games.iter().any(|game| game.get_answer_length() != games[0].get_answer_length()) {
+            return None;
+        }
+        return MultiGame::new_with_boards(games, max_guesses);
+    }
+
+    fn new_with_boards(boards: Vec<Game>, max_guesses: Option<u64>) -> Option<MultiGame> {
+        if boards.is_empty() {
+            return None;
+        }
+        return Some(MultiGame { boards, guess_count: 0, max_guesses, history: Vec::new(), state: GameState::InProgress });
+    }
+
+    // what the guesses are told on every board from now on, bulls and cows by default
+    pub fn set_feedback_kind(&mut self, feedback_kind: FeedbackKind) {
+        for board in self.boards.iter_mut() {
+            board.set_feedback_kind(feedback_kind);
+        }
+    }
+
+    pub fn get_boards_count(&self) -> usize {
+        return self.boards.len();
+    }
+
+    pub fn get_answers(&self) -> Vec<&str> {
+        return self.boards.iter().map(|board| board.get_answer()).collect();
+    }
+
+    pub fn get_answer_length(&self) -> usize {
+        return self.boards[0].get_answer_length();
+    }
+
+    pub fn is_solved(&self, board: usize) -> bool {
+        return self.boards[board].get_state() == GameState::Won;
+    }
+
+    pub fn get_solved_count(&self) -> usize {
+        return (0..self.boards.len()).filter(|&board| self.is_solved(board)).count();
+    }
+
+    pub fn get_guess_count(&self) -> u64 {
+        return self.guess_count;
+    }
+
+    pub fn get_max_guesses(&self) -> Option<u64> {
+        return self.max_guesses;
+    }
+
+    pub fn get_history(&self) -> &[(String, BoardFeedbacks)] {
+        return &self.history;
+    }
+
+    pub fn get_state(&self) -> GameState {
+        return self.state;
+    }
+
+    pub fn is_over(&self) -> bool {
+        return self.state != GameState::InProgress;
+    }
+
+    pub fn abandon(&mut self) {
+        if self.state == GameState::InProgress {
+            self.state = GameState::Abandoned;
+        }
+    }
+
+    // the feedback on every board, None on the boards already solved;
+    // returns None for invalid guesses and once the game is over
+    pub fn make_guess(&mut self, guess: &str) -> Option<BoardFeedbacks> {
+        if self.is_over() {
+            return None;
+        }
+        // the boards share the variant, a guess valid for one is valid for all
+        let first: usize = (0..self.boards.len()).find(|&board| !self.is_solved(board))?;
+        let first_feedback: (usize, usize) = self.boards[first].make_guess(guess)?;
+
+        self.guess_count += 1;
+        let mut feedbacks: BoardFeedbacks = Vec::new();
+        for board in 0..self.boards.len() {
+            if board == first {
+                feedbacks.push(Some(first_feedback));
+            } else if self.is_solved(board) {
+                feedbacks.push(None);
+            } else {
+                feedbacks.push(self.boards[board].make_guess(guess));
+            }
+        }
+        self.history.push((String::from(guess), feedbacks.clone()));
+
+        if self.get_solved_count() == self.boards.len() {
+            self.state = GameState::Won;
+        } else if self.max_guesses.is_some_and(|max_guesses| self.guess_count >= max_guesses) {
+            self.state = GameState::Lost;
+        }
+        return Some(feedbacks);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::algos::algos_utils::generate_init_values_for_numbers;
+    use crate::algos::multi_secret_algo::MultiSecretAlgo;
+    use crate::game::GameState;
+    use crate::ruleset::Ruleset;
+    use super::MultiGame;
+
+    fn digits() -> Vec<char> {
+        return "0123456789".chars().collect();
+    }
+
+    #[test]
+    fn test_multi_game() {
+        let mut game = MultiGame::new_with_answers(&["123", "456"], &digits(), false, None).unwrap();
+        assert_eq!(game.get_boards_count(), 2);
+        assert_eq!(game.make_guess("112"), None);
+        assert_eq!(game.make_guess("124"), Some(vec![Some((2, 0)), Some((0, 1))]));
+        assert_eq!(game.make_guess("123"), Some(vec![Some((3, 0)), Some((0, 0))]));
+        assert!(game.is_solved(0));
+        assert_eq!(game.make_guess("456"), Some(vec![None, Some((3, 0))]));
+        assert_eq!(game.get_state(), GameState::Won);
+        assert_eq!(game.get_guess_count(), 3);
+        assert_eq!(game.get_history().len(), 3);
+        assert_eq!(game.make_guess("456"), None);
+
+        assert!(MultiGame::new_with_answers(&["123", "4567"], &digits(), false, None).is_none());
+        assert!(MultiGame::new_with_answers(&[], &digits(), false, None).is_none());
+        let game = MultiGame::new_with_options(4, 4, &digits(), false, None, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(game.get_answers().len(), 4);
+    }
+    #[test]
+    fn test_multi_game_guess_limit() {
+        let mut game = MultiGame::new_with_answers(&["123", "456"], &digits(), false, Some(2)).unwrap();
+        game.make_guess("123");
+        assert_eq!(game.get_state(), GameState::InProgress);
+        game.make_guess("789");
+        assert_eq!(game.get_state(), GameState::Lost);
+        assert_eq!(game.get_solved_count(), 1);
+    }
+    #[test]
+    fn test_solver_plays_multi_game() {
+        let mut game = MultiGame::new_with_options(4, 3, &digits(), false, None, &mut StdRng::seed_from_u64(7)).unwrap();
+        let mut solver = MultiSecretAlgo::new(generate_init_values_for_numbers(3), &Ruleset::new(3, &digits(), false), 4).unwrap();
+        while !game.is_over() {
+            let guess = solver.guess().unwrap();
+            let feedbacks = game.make_guess(&guess).unwrap();
+            assert_eq!(solver.incorporate_guess_feedback(&feedbacks), Some(true));
+        }
+        assert_eq!(game.get_state(), GameState::Won);
+    }
+}
@@ -0,0 +1,342 @@
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use crate::algos::algos_utils;
+use crate::algos::feedback::{self, FeedbackKind};
+use crate::dictionary;
+use crate::mastermind;
+
+// The rules of a variant: what a secret is made of, what the keeper tells and how many guesses
+// the player has. Games, the candidate numbers and the solvers are all made from a ruleset.
+// Besides the presets, rulesets are read from TOML files like
+//   preset = "hex"        # start from a preset, the classic game otherwise
+//   length = 5
+//   alphabet = "0123456789abcdef"
+//   repeats = false
+//   feedback = "bulls-and-cows"
+//   max_guesses = 12
+//   no_leading = "0"      # symbols the secret and the guesses cannot start with
+//   words = "words.txt"   # play with the words of this file instead, relative to the ruleset file
+
+pub const PRESET_NAMES: [&str; 4] = ["classic", "easy", "hex", "mastermind"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ruleset {
+    pub length: usize,
+    pub alphabet: Vec<char>,
+    pub repeats: bool,
+    pub feedback_kind: FeedbackKind,
+    // None means the game only ends when the player wins or gives up
+    pub max_guesses: Option<u64>,
+    // symbols a secret or a guess cannot start with, like the 0 of numbers written without leading zeros
+    pub no_leading: Vec<char>,
+    // the only secrets and guesses allowed when playing with words, sorted, see `dictionary`
+    pub words: Option<Vec<String>>,
+}
+
+// a ruleset file, every key is optional
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesetFile {
+    preset: Option<String>,
+    length: Option<usize>,
+    alphabet: Option<String>,
+    repeats: Option<bool>,
+    feedback: Option<String>,
+    max_guesses: Option<u64>,
+    no_leading: Option<String>,
+    words: Option<String>,
+}
+
+impl Default for Ruleset {
+    fn default() -> Ruleset {
+        return Ruleset::classic();
+    }
+}
+
+impl Ruleset {
+    // bulls and cows of the given length over the alphabet, without guess limit
+    pub fn new(length: usize, alphabet: &[char], repeats: bool) -> Ruleset {
+        return Ruleset { length, alphabet: alphabet.to_vec(), repeats, feedback_kind: FeedbackKind::BullsAndCows, max_guesses: None, no_leading: Vec::new(), words: None };
+    }
+
+    // 4 different digits
+    pub fn classic() -> Ruleset {
+        let digits: Vec<char> = algos_utils::DEFAULT_ALPHABET.chars().collect();
+        return Ruleset::new(4, &digits, false);
+    }
+
+    // the words of the list, which must all have the same length and no repeated letter; None without words
+    pub fn from_words(mut words: Vec<String>) -> Option<Ruleset> {
+        words.sort();
+        words.dedup();
+        let length = words.first()?.chars().count();
+        let rules = Ruleset { words: None, ..Ruleset::new(length, &dictionary::get_alphabet(&words), false) };
+        if !words.iter().all(|word| rules.fits(word)) {
+            return None;
+        }
+        return Some(Ruleset { words: Some(words), ..rules });
+    }
+
+    // one of PRESET_NAMES, the mastermind board has 10 rows
+    pub fn from_preset(name: &str) -> Option<Ruleset> {
+        match name {
+            "classic" => return Some(Ruleset::classic()),
+            "easy" => return Some(Ruleset { length: 3, ..Ruleset::classic() }),
+            "hex" => return Some(Ruleset::new(4, &"0123456789abcdef".chars().collect::<Vec<char>>(), false)),
+            "mastermind" => {
+                let colors = mastermind::get_color_alphabet(mastermind::DEFAULT_COLORS)?;
+                return Some(Ruleset { max_guesses: Some(10), ..Ruleset::new(mastermind::DEFAULT_PEGS, &colors, true) });
+            }
+            _ => return None,
+        }
+    }
+
+    // word files are looked up from the current directory
+    pub fn from_toml(text: &str) -> Result<Ruleset, String> {
+        return Ruleset::parse(text, Path::new(""));
+    }
+
+    pub fn load(path: &Path) -> Result<Ruleset, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        return Ruleset::parse(&text, dir).map_err(|message| format!("{}: {}", path.display(), message));
+    }
+
+    fn parse(text: &str, dir: &Path) -> Result<Ruleset, String> {
+        let file: RulesetFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        let mut rules = match &file.preset {
+            Some(name) => Ruleset::from_preset(name).ok_or(format!("unknown preset {}, expected one of {}", name, PRESET_NAMES.join(", ")))?,
+            None => Ruleset::classic(),
+        };
+        if let Some(length) = file.length {
+            rules.length = length;
+        }
+        if let Some(alphabet) = &file.alphabet {
+            rules.alphabet = alphabet.chars().collect();
+        }
+        if let Some(repeats) = file.repeats {
+            rules.repeats = repeats;
+        }
+        if let Some(name) = &file.feedback {
+            rules.feedback_kind = FeedbackKind::from_name(name).ok_or(format!("unknown feedback {}", name))?;
+        }
+        if file.max_guesses.is_some() {
+            rules.max_guesses = file.max_guesses;
+        }
+        if let Some(no_leading) = &file.no_leading {
+            rules.no_leading = no_leading.chars().collect();
+        }
+        if let Some(words) = &file.words {
+            if file.alphabet.is_some() || file.repeats.is_some() {
+                return Err(String::from("words come with their own alphabet and no repeats"));
+            }
+            let path = dir.join(words);
+            let words = dictionary::load_words(&path, rules.length).map_err(|e| format!("cannot read words from {}: {}", path.display(), e))?;
+            let word_rules = Ruleset::from_words(words).ok_or(format!("{} has no word of {} different letters", path.display(), rules.length))?;
+            rules = Ruleset { feedback_kind: rules.feedback_kind, max_guesses: rules.max_guesses, no_leading: rules.no_leading, ..word_rules };
+        }
+        rules.validate()?;
+        return Ok(rules);
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.length == 0 {
+            return Err(String::from("length must be positive"));
+        }
+        if self.alphabet.is_empty() || self.alphabet.iter().collect::<HashSet<&char>>().len() != self.alphabet.len() {
+            return Err(format!("alphabet \"{}\" must be non-empty and contain no duplicates", self.alphabet.iter().collect::<String>()));
+        }
+        if !self.repeats && self.length > self.alphabet.len() {
+            return Err(format!("length {} is longer than the alphabet without repeats", self.length));
+        }
+        if self.feedback_kind == FeedbackKind::Positional && self.length > feedback::MAX_POSITIONAL_LENGTH {
+            return Err(format!("positional feedback is limited to {} symbols", feedback::MAX_POSITIONAL_LENGTH));
+        }
+        if !self.no_leading.iter().all(|symbol| self.alphabet.contains(symbol)) || self.get_leading_symbols().is_empty() {
+            return Err(format!("no_leading \"{}\" must be symbols of the alphabet and leave some to start with", self.no_leading.iter().collect::<String>()));
+        }
+        if self.words.as_ref().is_some_and(|words| words.is_empty() || !words.iter().all(|word| self.fits(word))) {
+            return Err(String::from("the words must be non-empty and fit the rules"));
+        }
+        return Ok(());
+    }
+
+    // names the variant wherever games of different rules must not be compared, e.g. "4/0123456789"
+    // for the classic game or "5/0123456789abcdef/repeats/total/max12/no0"; word lists are told apart
+    // by a hash of their words
+    pub fn get_key(&self) -> String {
+        let mut parts: Vec<String> = vec![self.length.to_string(), self.alphabet.iter().collect()];
+        if self.repeats {
+            parts.push(String::from("repeats"));
+        }
+        if self.feedback_kind != FeedbackKind::BullsAndCows {
+            parts.push(String::from(self.feedback_kind.get_name()));
+        }
+        if let Some(max_guesses) = self.max_guesses {
+            parts.push(format!("max{}", max_guesses));
+        }
+        if !self.no_leading.is_empty() {
+            parts.push(format!("no{}", self.no_leading.iter().collect::<String>()));
+        }
+        if let Some(words) = &self.words {
+            parts.push(format!("words{:016x}", hash_words(words)));
+        }
+        return parts.join("/");
+    }
+
+    // the symbols a secret can start with
+    pub fn get_leading_symbols(&self) -> Vec<char> {
+        return self.alphabet.iter().filter(|symbol| !self.no_leading.contains(symbol)).copied().collect();
+    }
+
+    // whether the number is a possible secret, and so a valid guess
+    pub fn is_valid(&self, number: &str) -> bool {
+        return self.fits(number) && self.words.as_ref().is_none_or(|words| words.binary_search_by(|word| word.as_str().cmp(number)).is_ok());
+    }
+
+    // the rules without the words
+    fn fits(&self, number: &str) -> bool {
+        let symbols: Vec<char> = number.chars().collect();
+        return symbols.len() == self.length
+            && symbols.iter().all(|symbol| self.alphabet.contains(symbol))
+            && (self.repeats || symbols.iter().collect::<HashSet<&char>>().len() == symbols.len())
+            && !symbols.first().is_some_and(|symbol| self.no_leading.contains(symbol));
+    }
+
+    // number of possible secrets, saturating on overflow
+    pub fn get_numbers_count(&self) -> u64 {
+        if let Some(words) = &self.words {
+            return words.len() as u64;
+        }
+        let symbols = self.alphabet.len() as u64;
+        let mut count: u64 = self.get_leading_symbols().len() as u64;
+        for i in 1..self.length as u64 {
+            let factor = if self.repeats { symbols } else { symbols.saturating_sub(i) };
+            count = count.saturating_mul(factor);
+        }
+        return count;
+    }
+
+    // all possible secrets in alphabet order
+    pub fn generate_numbers(&self) -> Vec<String> {
+        if let Some(words) = &self.words {
+            return words.clone();
+        }
+        let mut numbers = algos_utils::generate_numbers(&self.alphabet, self.length, self.repeats);
+        if !self.no_leading.is_empty() {
+            numbers.retain(|number| number.chars().next().is_some_and(|symbol| !self.no_leading.contains(&symbol)));
+        }
+        return numbers;
+    }
+
+    // a random secret, every one as likely; None when the rules are not valid
+    pub fn choose_secret<R: Rng>(&self, rng: &mut R) -> Option<String> {
+        self.validate().ok()?;
+        if let Some(words) = &self.words {
+            return words.choose(rng).cloned();
+        }
+        if self.no_leading.is_empty() {
+            if self.repeats {
+                return Some((0..self.length).map(|_| *self.alphabet.choose(rng).unwrap()).collect());
+            }
+            return Some(self.alphabet.choose_multiple(rng, self.length).collect());
+        }
+        // as many secrets start with every allowed symbol
+        let first = *self.get_leading_symbols().choose(rng).unwrap();
+        let mut secret = String::from(first);
+        if self.repeats {
+            secret.extend((1..self.length).map(|_| *self.alphabet.choose(rng).unwrap()));
+        } else {
+            let rest: Vec<char> = self.alphabet.iter().filter(|&&symbol| symbol != first).copied().collect();
+            secret.extend(rest.choose_multiple(rng, self.length - 1));
+        }
+        return Some(secret);
+    }
+}
+
+// FNV-1a, stable from one build to the next unlike the standard library's hasher
+fn hash_words(words: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in words.iter().flat_map(|word| word.bytes().chain(std::iter::once(b'\n'))) {
+        hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+    }
+    return hash;
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use super::{Ruleset, PRESET_NAMES};
+    use crate::algos::feedback::FeedbackKind;
+
+    #[test]
+    fn test_presets() {
+        for name in PRESET_NAMES {
+            let rules = Ruleset::from_preset(name).unwrap();
+            assert!(rules.validate().is_ok());
+            assert_eq!(rules.generate_numbers().len() as u64, rules.get_numbers_count());
+        }
+        assert_eq!(Ruleset::from_preset("classic").unwrap().get_numbers_count(), 5040);
+        assert_eq!(Ruleset::from_preset("easy").unwrap().get_numbers_count(), 720);
+        assert_eq!(Ruleset::from_preset("hex").unwrap().get_numbers_count(), 43680);
+        assert_eq!(Ruleset::from_preset("mastermind").unwrap().get_numbers_count(), 1296);
+        assert!(Ruleset::from_preset("chess").is_none());
+    }
+    #[test]
+    fn test_get_key() {
+        assert_eq!(Ruleset::classic().get_key(), "4/0123456789");
+        assert_eq!(Ruleset::new(5, &['a', 'b'], true).get_key(), "5/ab/repeats");
+        assert_eq!(Ruleset::from_preset("mastermind").unwrap().get_key(), "4/RGBYOP/repeats/max10");
+        let rules = Ruleset { feedback_kind: FeedbackKind::AnyBull, no_leading: vec!['0'], ..Ruleset::classic() };
+        assert_eq!(rules.get_key(), "4/0123456789/any-bull/no0");
+        let words = Ruleset::from_words(vec![String::from("fish"), String::from("bird")]).unwrap();
+        let other_words = Ruleset::from_words(vec![String::from("fish"), String::from("bard")]).unwrap();
+        assert!(words.get_key().starts_with("4/bdfhirs/words"));
+        assert_ne!(words.get_key(), Ruleset::from_words(vec![String::from("fish")]).unwrap().get_key());
+        assert_ne!(words.get_key(), other_words.get_key());
+    }
+    #[test]
+    fn test_from_toml() {
+        let rules = Ruleset::from_toml("length = 3\nno_leading = \"0\"\nfeedback = \"total\"\nmax_guesses = 12\n").unwrap();
+        assert_eq!(rules.length, 3);
+        assert_eq!(rules.feedback_kind, FeedbackKind::Total);
+        assert_eq!(rules.max_guesses, Some(12));
+        assert_eq!(rules.get_numbers_count(), 648);
+        assert_eq!(rules.generate_numbers().len(), 648);
+        assert!(rules.is_valid("102") && !rules.is_valid("012") && !rules.is_valid("1022"));
+
+        let rules = Ruleset::from_toml("preset = \"mastermind\"\nlength = 5\n").unwrap();
+        assert!(rules.repeats && rules.length == 5 && rules.alphabet.len() == 6);
+
+        assert!(Ruleset::from_toml("length = 0").is_err());
+        assert!(Ruleset::from_toml("lenght = 3").is_err());
+        assert!(Ruleset::from_toml("preset = \"chess\"").is_err());
+        assert!(Ruleset::from_toml("alphabet = \"abc\"").is_err());
+        assert!(Ruleset::from_toml("no_leading = \"0123456789\"").is_err());
+        assert!(Ruleset::from_toml("length = 20\nalphabet = \"abcdefghijklmnopqrst\"\nfeedback = \"positional\"").is_ok());
+        assert!(Ruleset::from_toml("length = 21\nalphabet = \"abcdefghijklmnopqrstu\"\nfeedback = \"positional\"").is_err());
+    }
+    #[test]
+    fn test_choose_secret() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let rules = Ruleset { no_leading: vec!['0', '1'], ..Ruleset::new(3, &['0', '1', '2', '3'], false) };
+        for _ in 0..20 {
+            assert!(rules.is_valid(&rules.choose_secret(&mut rng).unwrap()));
+        }
+        let rules = Ruleset { repeats: true, ..rules };
+        for _ in 0..20 {
+            assert!(rules.is_valid(&rules.choose_secret(&mut rng).unwrap()));
+        }
+        assert!(Ruleset::new(5, &['0', '1'], false).choose_secret(&mut rng).is_none());
+
+        let words: Vec<String> = ["farm", "cows", "farm"].iter().map(|word| String::from(*word)).collect();
+        let rules = Ruleset::from_words(words).unwrap();
+        assert_eq!(rules.generate_numbers(), vec!["cows", "farm"]);
+        assert!(rules.is_valid("cows") && !rules.is_valid("crow"));
+        assert!(rules.is_valid(&rules.choose_secret(&mut rng).unwrap()));
+        assert!(Ruleset::from_words(vec![String::from("moon")]).is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt;
+use crate::algos::feedback::FeedbackKind;
+use crate::clues;
+
+// Parser for game logs written in the format used in the README:
+//
+// Number to guess: 8536
+//
+// 9435 1b 1c
+// 3865 0b 4c
+// 8536 4b 0c
+//
+// The "Number to guess" header is optional, blank lines are ignored.
+// Feedback can also be written as Pico-Fermi-Bagels clues, e.g. "3865 Pico Pico Pico Pico",
+// or as the other kinds of feedback write it, e.g. "3865 4 found" (see `FeedbackKind::format`).
+
+const SECRET_HEADER: &str = "Number to guess:";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptEntry {
+    pub line: usize,
+    pub guess: String,
+    pub feedback: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    pub secret: Option<String>,
+    pub entries: Vec<TranscriptEntry>,
+    // what the feedback of the entries tells
+    pub feedback_kind: FeedbackKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptError {
+    pub line: usize,
+    pub content: String,
+    // the kind of feedback the line was expected to carry
+    pub feedback_kind: FeedbackKind,
+}
+
+impl TranscriptError {
+    // the shape of a line of the expected kind
+    pub fn get_expected_format(&self) -> &'static str {
+        match self.feedback_kind {
+            FeedbackKind::BullsAndCows => return "NNNN Xb Yc",
+            FeedbackKind::BullsOnly => return "NNNN Xb",
+            FeedbackKind::Total => return "NNNN X found",
+            FeedbackKind::AnyBull => return "NNNN a bull\" or \"NNNN no bull",
+            FeedbackKind::Positional => return "NNNN BC--",
+        }
+    }
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: cannot parse \"{}\", expected \"{}\"", self.line, self.content, self.get_expected_format())
+    }
+}
+
+// entry whose recorded feedback differs from the one computed against the secret
+// actual is None when the guess cannot be scored against the secret at all (length mismatch)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayMismatch {
+    pub entry: TranscriptEntry,
+    pub actual: Option<(usize, usize)>,
+}
+
+pub fn format_line(guess: &str, feedback: (usize, usize)) -> String {
+    return format!("{} {}b {}c", guess, feedback.0, feedback.1);
+}
+
+// accepts "1b 2c", "1 2" and Pico-Fermi-Bagels clues such as "Fermi Pico Pico"
+pub fn parse_feedback(text: &str) -> Option<(usize, usize)> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if tokens.len() != 2 || !tokens.iter().all(|token| token.starts_with(|c: char| c.is_ascii_digit())) {
+        return clues::parse_clues(text);
+    }
+    let bulls: usize = tokens[0].strip_suffix('b').unwrap_or(tokens[0]).parse().ok()?;
+    let cows: usize = tokens[1].strip_suffix('c').unwrap_or(tokens[1]).parse().ok()?;
+    return Some((bulls, cows));
+}
+
+// "1234 1b 2c" or "1234 Fermi Pico Pico"
+pub fn parse_line(line: &str) -> Option<(String, (usize, usize))> {
+    return parse_line_of_kind(line, FeedbackKind::BullsAndCows);
+}
+
+// the guess and its feedback as `FeedbackKind::parse` reads it, e.g. "1234 2 found" or "1234 -CB-"
+pub fn parse_line_of_kind(line: &str, kind: FeedbackKind) -> Option<(String, (usize, usize))> {
+    let (guess, feedback) = line.trim().split_once(char::is_whitespace)?;
+    return Some((String::from(guess), kind.parse(feedback, guess.chars().count())?));
+}
+
+pub fn parse_transcript(text: &str) -> Result<Transcript, TranscriptError> {
+    return parse_transcript_of_kind(text, FeedbackKind::BullsAndCows);
+}
+
+pub fn parse_transcript_of_kind(text: &str, kind: FeedbackKind) -> Result<Transcript, TranscriptError> {
+    let mut secret: Option<String> = None;
+    let mut entries: Vec<TranscriptEntry> = Vec::new();
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix(SECRET_HEADER) {
+            secret = Some(String::from(header.trim()));
+            continue;
+        }
+        match parse_line_of_kind(line, kind) {
+            Some((guess, feedback)) => entries.push(TranscriptEntry { line: index + 1, guess, feedback }),
+            None => return Err(TranscriptError { line: index + 1, content: String::from(line), feedback_kind: kind }),
+        }
+    }
+
+    return Ok(Transcript { secret, entries, feedback_kind: kind });
+}
+
+impl Transcript {
+    pub fn get_guess_length(&self) -> Option<usize> {
+        return self.entries.first().map(|entry| entry.guess.chars().count());
+    }
+
+    pub fn check_against_secret(&self, secret: &str) -> Vec<ReplayMismatch> {
+        let mut mismatches: Vec<ReplayMismatch> = Vec::new();
+        for entry in self.entries.iter() {
+            let actual = self.feedback_kind.get_feedback(&entry.guess, secret);
+            if actual != Some(entry.feedback) {
+                mismatches.push(ReplayMismatch { entry: entry.clone(), actual });
+            }
+        }
+        return mismatches;
+    }
+
+    pub fn find_consistent_secrets(&self, numbers: Vec<String>) -> Vec<String> {
+        return numbers.into_iter().filter(|number| {
+            self.entries.iter().all(|entry| {
+                self.feedback_kind.get_feedback(&entry.guess, number) == Some(entry.feedback)
+            })
+        }).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    const README_GAME: &str = "Number to guess: 8536\n\n9435 1b 1c\n3865 0b 4c\n6538 2b 2c\n5638 1b 3c\n8536 4b 0c\n";
+
+    #[test]
+    fn test_parse_line() {
+        use super::parse_line;
+        assert_eq!(parse_line("9435 1b 1c"), Some((String::from("9435"), (1, 1))));
+        assert_eq!(parse_line("  3865   0b 4c "), Some((String::from("3865"), (0, 4))));
+        assert_eq!(parse_line("9435 1c 1b"), None);
+        assert_eq!(parse_line("9435 1b"), None);
+        assert_eq!(parse_line("9435 xb 1c"), None);
+        assert_eq!(parse_line("9435 1b 1c extra"), None);
+        assert_eq!(parse_line("9435 Fermi Pico"), Some((String::from("9435"), (1, 1))));
+        assert_eq!(parse_line("1270 Bagels"), Some((String::from("1270"), (0, 0))));
+        assert_eq!(parse_line("9435 Fermi 1c"), None);
+    }
+    #[test]
+    fn test_parse_line_of_kind() {
+        use super::parse_line_of_kind;
+        use crate::algos::feedback::FeedbackKind;
+        assert_eq!(parse_line_of_kind("0123 1 found", FeedbackKind::Total), Some((String::from("0123"), (0, 1))));
+        assert_eq!(parse_line_of_kind("0123 4b 0c", FeedbackKind::Total), Some((String::from("0123"), (4, 0))));
+        assert_eq!(parse_line_of_kind("0123 2b", FeedbackKind::BullsOnly), Some((String::from("0123"), (2, 0))));
+        assert_eq!(parse_line_of_kind("0123 no bull", FeedbackKind::AnyBull), Some((String::from("0123"), (0, 0))));
+        assert_eq!(parse_line_of_kind("0123 ---C", FeedbackKind::Positional), Some((String::from("0123"), FeedbackKind::Positional.get_feedback("0123", "3456").unwrap())));
+        assert_eq!(parse_line_of_kind("0123 -C-", FeedbackKind::Positional), None);
+        assert_eq!(parse_line_of_kind("éèêa 1 found", FeedbackKind::Total), Some((String::from("éèêa"), (0, 1))));
+        assert_eq!(parse_line_of_kind("0123 1 found", FeedbackKind::BullsAndCows), None);
+        assert_eq!(parse_line_of_kind("0123", FeedbackKind::Total), None);
+    }
+    #[test]
+    fn test_parse_feedback() {
+        use super::parse_feedback;
+        assert_eq!(parse_feedback("1b 2c"), Some((1, 2)));
+        assert_eq!(parse_feedback(" 1 2 "), Some((1, 2)));
+        assert_eq!(parse_feedback("1b"), None);
+        assert_eq!(parse_feedback("1c 2b"), None);
+        assert_eq!(parse_feedback("-1 2"), None);
+        assert_eq!(parse_feedback("Fermi Pico Pico"), Some((1, 2)));
+        assert_eq!(parse_feedback("Bagels"), Some((0, 0)));
+        assert_eq!(parse_feedback("Fermi Pico"), Some((1, 1)));
+        assert_eq!(parse_feedback("Pico Pico"), Some((0, 2)));
+        assert_eq!(parse_feedback("fermi Fermi"), Some((2, 0)));
+        assert_eq!(parse_feedback("1b Pico"), None);
+    }
+    #[test]
+    fn test_parse_transcript() {
+        use super::parse_transcript;
+        let transcript = parse_transcript(README_GAME).unwrap();
+        assert_eq!(transcript.secret, Some(String::from("8536")));
+        assert_eq!(transcript.entries.len(), 5);
+        assert_eq!(transcript.entries[0].line, 3);
+        assert_eq!(transcript.entries[4].guess, String::from("8536"));
+        assert_eq!(transcript.entries[4].feedback, (4, 0));
+        assert_eq!(transcript.get_guess_length(), Some(4));
+
+        let error = parse_transcript("9435 1b 1c\noops\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.content, String::from("oops"));
+    }
+    #[test]
+    fn test_check_against_secret() {
+        use super::parse_transcript;
+        let transcript = parse_transcript(README_GAME).unwrap();
+        assert_eq!(transcript.check_against_secret("8536").len(), 0);
+
+        let transcript = parse_transcript("9435 1b 1c\n3865 1b 3c\n").unwrap();
+        let mismatches = transcript.check_against_secret("8536");
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].entry.line, 2);
+        assert_eq!(mismatches[0].actual, Some((0, 4)));
+
+        let mismatches = transcript.check_against_secret("853");
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].actual, None);
+    }
+    #[test]
+    fn test_transcript_of_kind() {
+        use super::parse_transcript_of_kind;
+        use crate::algos::feedback::FeedbackKind;
+        let transcript = parse_transcript_of_kind("9435 2 found\n3865 4 found\n", FeedbackKind::Total).unwrap();
+        assert_eq!(transcript.check_against_secret("8536").len(), 0);
+        assert_eq!(transcript.check_against_secret("5836").len(), 0);
+        assert_eq!(transcript.check_against_secret("1234").len(), 1);
+        let transcript = parse_transcript_of_kind("éèêa 1 found\n", FeedbackKind::Total).unwrap();
+        assert_eq!(transcript.get_guess_length(), Some(4));
+
+        let error = parse_transcript_of_kind("9435 BC--\n3865 1b 1c\n", FeedbackKind::Positional).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.to_string(), "line 2: cannot parse \"3865 1b 1c\", expected \"NNNN BC--\"");
+        let error = parse_transcript_of_kind("9435\n", FeedbackKind::BullsAndCows).unwrap_err();
+        assert_eq!(error.to_string(), "line 1: cannot parse \"9435\", expected \"NNNN Xb Yc\"");
+    }
+    #[test]
+    fn test_find_consistent_secrets() {
+        use super::parse_transcript;
+        use crate::algos::algos_utils::generate_default_init_values_for_numbers;
+        let transcript = parse_transcript(README_GAME).unwrap();
+        let secrets = transcript.find_consistent_secrets(generate_default_init_values_for_numbers());
+        assert_eq!(secrets, vec![String::from("8536")]);
+
+        let transcript = parse_transcript("9435 1b 1c\n3865 0b 4c\n").unwrap();
+        let secrets = transcript.find_consistent_secrets(generate_default_init_values_for_numbers());
+        assert!(secrets.contains(&String::from("8536")));
+        assert!(secrets.len() > 1);
+
+        let transcript = parse_transcript("1234 4b 0c\n5678 1b 0c\n").unwrap();
+        let secrets = transcript.find_consistent_secrets(generate_default_init_values_for_numbers());
+        assert_eq!(secrets.len(), 0);
+    }
+}
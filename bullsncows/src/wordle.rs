@@ -0,0 +1,34 @@
+use crossterm::style::Stylize;
+use crate::algos::feedback::Mark;
+
+// The positional feedback marks every symbol of the guess like Wordle does: a bull on green,
+// a cow on yellow and a symbol the secret does not have (or has no copy of left) on grey.
+
+// the symbols of the guess on the colors of their marks
+pub fn render_marks(guess: &str, marks: &[Mark]) -> String {
+    return guess.chars().zip(marks.iter()).map(|(symbol, mark)| {
+        let cell: String = format!(" {} ", symbol);
+        match mark {
+            Mark::Bull => cell.black().on_green().to_string(),
+            Mark::Cow => cell.black().on_yellow().to_string(),
+            Mark::Absent => cell.white().on_dark_grey().to_string(),
+        }
+    }).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::feedback::{get_marks, Mark};
+    use super::render_marks;
+
+    #[test]
+    fn test_render_marks() {
+        let marks = get_marks("1235", "1532").unwrap();
+        assert_eq!(marks, vec![Mark::Bull, Mark::Cow, Mark::Bull, Mark::Cow]);
+        let rendered = render_marks("1235", &marks);
+        for symbol in ['1', '2', '3', '5'] {
+            assert!(rendered.contains(symbol));
+        }
+        assert_ne!(rendered, render_marks("1235", &[Mark::Absent; 4]));
+    }
+}
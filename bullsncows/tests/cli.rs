@@ -0,0 +1,528 @@
+#![allow(clippy::needless_return)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Command, Output, Stdio};
+
+fn bullsncows(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bullsncows"))
+        .args(args)
+        .env_remove("BULLSNCOWS_DEBUG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("cannot start bullsncows");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    return child.wait_with_output().unwrap();
+}
+
+fn stdout_of(output: &Output) -> String {
+    return String::from_utf8(output.stdout.clone()).unwrap();
+}
+
+#[test]
+fn test_help_lists_subcommands() {
+    let output = bullsncows(&["--help"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    for command in ["play", "tui", "solve", "assist", "bench", "engine", "serve", "optimal", "static", "replay"] {
+        assert!(stdout.contains(command), "{} missing from --help", command);
+    }
+
+    let output = bullsncows(&["solve", "--help"], "");
+    let stdout = stdout_of(&output);
+    for option in ["--length", "--alphabet", "--repeats", "--solver", "--seed", "--max-guesses", "--format"] {
+        assert!(stdout.contains(option), "{} missing from solve --help", option);
+    }
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let output = bullsncows(&["solve", "--solver", "nonsense"], "");
+    assert!(!output.status.success());
+    let output = bullsncows(&["solve", "--length", "11"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let output = bullsncows(&["solve", "--alphabet", "aab", "--length", "2"], "");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_solve() {
+    let output = bullsncows(&["solve", "--length", "3", "--secret", "123"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Number to guess: 123\n"));
+    assert!(stdout.trim_end().ends_with("123 3b 0c"));
+
+    let output = bullsncows(&["solve", "--length", "3", "--alphabet", "abcdef", "--solver", "baseline", "--seed", "3", "--format", "json"], "");
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    assert_eq!(result["solved"], true);
+    assert_eq!(result["solver"], "baseline");
+    let guesses = result["guesses"].as_array().unwrap();
+    assert_eq!(guesses.last().unwrap()["guess"], result["secret"]);
+
+    let again = bullsncows(&["solve", "--length", "3", "--alphabet", "abcdef", "--solver", "baseline", "--seed", "3", "--format", "json"], "");
+    assert_eq!(stdout_of(&again), stdout_of(&output));
+}
+
+#[test]
+fn test_solve_with_mcts() {
+    let output = bullsncows(&["solve", "--length", "3", "--secret", "123", "--solver", "mcts", "--iterations", "100", "--seed", "1"], "");
+    assert!(output.status.success());
+    assert!(stdout_of(&output).contains("123 3b 0c"));
+    let output = bullsncows(&["solve", "--solver", "mcts", "--iterations", "100", "--time-limit", "10"], "");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_solve_with_budget() {
+    for solver in ["entropy", "entropy-2", "worst-case"] {
+        let output = bullsncows(&["solve", "--secret", "9876", "--solver", solver, "--time-limit", "5"], "");
+        assert!(output.status.success(), "{}", solver);
+        assert!(stdout_of(&output).contains("9876 4b 0c"));
+    }
+    let output = bullsncows(&["solve", "--secret", "9876", "--solver", "entropy-2", "--iterations", "50"], "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_mastermind() {
+    let output = bullsncows(&["solve", "--colors", "6", "--secret", "PYOB", "--solver", "worst-case"], "");
+    assert!(output.status.success());
+    assert!(stdout_of(&output).trim_end().ends_with("PYOB 4b 0c"));
+
+    let output = bullsncows(&["play", "--colors", "6", "--seed", "5", "--debug"], "rrgg\nRRG\nquit\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Break the code (4 pegs of colors RGBYOP"));
+    assert!(stdout.contains("black: "));
+    assert!(stdout.contains("Invalid input!"));
+    assert!(stdout.contains("Game abandoned. The code was "));
+
+    let output = bullsncows(&["solve", "--colors", "6", "--alphabet", "abc"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let output = bullsncows(&["solve", "--colors", "9"], "");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_rulesets() {
+    let output = bullsncows(&["solve", "--preset", "easy", "--secret", "789"], "");
+    assert!(output.status.success());
+    assert!(stdout_of(&output).trim_end().ends_with("789 3b 0c"));
+    let output = bullsncows(&["play", "--preset", "mastermind", "--seed", "5"], "quit\n");
+    assert!(stdout_of(&output).starts_with("Break the code (4 pegs of colors RGBYOP, 10 guesses"));
+    let output = bullsncows(&["solve", "--preset", "hex", "--length", "3"], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    let path = std::env::temp_dir().join(format!("bullsncows_rules_{}.toml", std::process::id()));
+    std::fs::write(&path, "length = 3\nno_leading = \"0\"\nfeedback = \"bulls\"\n").unwrap();
+    let file = path.to_str().unwrap();
+    let output = bullsncows(&["solve", "--rules", file, "--secret", "102", "--format", "json"], "");
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    assert_eq!(result["solved"], true);
+    let output = bullsncows(&["solve", "--rules", file, "--secret", "012"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let output = bullsncows(&["play", "--rules", file, "--seed", "1"], "012\nquit\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("not starting with 0"));
+    assert!(stdout.contains("Invalid input!"));
+
+    std::fs::write(&path, "lenght = 3\n").unwrap();
+    let output = bullsncows(&["solve", "--rules", file], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("lenght"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_solve_with_guess_limit() {
+    let output = bullsncows(&["solve", "--length", "3", "--solver", "baseline", "--secret", "123", "--seed", "1", "--max-guesses", "1"], "");
+    let stdout = stdout_of(&output);
+    if stdout.contains("123 3b 0c") {
+        assert!(output.status.success());
+    } else {
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(stdout.lines().filter(|line| line.ends_with('c')).count(), 1);
+    }
+}
+
+#[test]
+fn test_play() {
+    let output = bullsncows(&["play", "--length", "3", "--seed", "5", "--debug"], "");
+    let stdout = stdout_of(&output);
+    let answer = stdout.lines().find_map(|line| line.strip_prefix("[debug] answer: ")).unwrap().to_string();
+
+    let input = format!("12\nhint 1\n{}\nn\n", answer);
+    let output = bullsncows(&["play", "--length", "3", "--seed", "5", "--debug"], &input);
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Invalid input!"));
+    assert!(stdout.contains("Hint: 720 possible secrets remain"));
+    assert!(stdout.contains("You won after 1 guesses"));
+    assert!(stdout.contains("(1 hints used, 0s, difficulty 0.77)"));
+    assert!(stdout.contains("Games: 1, won: 1"));
+
+    let output = bullsncows(&["play", "--length", "3", "--seed", "5"], "quit\n");
+    let stdout = stdout_of(&output);
+    assert!(!stdout.contains("[debug]"));
+    assert!(stdout.contains(&format!("Game abandoned. The number was {}", answer)));
+
+    let output = bullsncows(&["play", "--length", "9", "--alphabet", "0123456789abcdefghijklmnopqrstuvwxyz"], "hint 1\nquit\n");
+    assert!(output.status.success());
+    assert!(stdout_of(&output).contains("No hints for this variant: variant has 34162713446400 possible secrets, at most 2000000 are supported by the solvers"));
+
+    let output = bullsncows(&["play", "--length", "3"], &format!("{}\n", "1".repeat(5000)));
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line longer than 4096 bytes"));
+}
+
+#[test]
+fn test_assist() {
+    let output = bullsncows(&["assist", "--length", "3"], "0 0\n3b 0c\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Try 012 (720 possible numbers left)"));
+    assert!(stdout.contains("Solved: "));
+}
+
+#[test]
+fn test_assist_corrects_feedback() {
+    let try_lines = |input: &str| -> Vec<String> {
+        let output = bullsncows(&["assist", "--length", "3"], input);
+        return stdout_of(&output).lines().filter(|line| line.starts_with("Try ")).map(String::from).collect();
+    };
+    let expected = try_lines("1 1\n");
+    let corrected = try_lines("1 2\nfix 1 1 1\n");
+    assert_eq!(corrected.len(), 3);
+    assert_eq!(corrected[2], expected[1]);
+    let undone = try_lines("1 2\nundo\nfix 1 1 1\n");
+    assert_eq!(undone, vec![expected[0].clone(), corrected[1].clone(), expected[0].clone()]);
+}
+
+#[test]
+fn test_engine() {
+    let output = bullsncows(&["engine", "--length", "3"], "guess\nfeedback 1 1\ncount\nfeedback 1 1\nguess\nnew\ncount\nbogus\n");
+    let lines: Vec<String> = stdout_of(&output).lines().map(String::from).collect();
+    assert_eq!(lines[0], "guess 012");
+    assert!(lines[1].starts_with("ok "));
+    assert_eq!(lines[2].replace("count", "ok"), lines[1]);
+    assert_eq!(lines[3], "error no guess to give feedback for");
+    assert!(lines[4].starts_with("guess "));
+    assert_eq!(lines[5], "ok");
+    assert_eq!(lines[6], "count 720");
+    assert_eq!(lines[7], "error unknown command");
+
+    let output = bullsncows(&["--format", "json", "engine", "--length", "3"], "guess\n");
+    let result: serde_json::Value = serde_json::from_str(stdout_of(&output).trim()).unwrap();
+    assert_eq!(result["guess"], "012");
+}
+
+#[test]
+fn test_bench() {
+    let output = bullsncows(&["bench", "--length", "3", "--solver", "baseline", "--games", "5", "--seed", "1", "--format", "json"], "");
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    assert_eq!(result["games"], 5);
+    assert_eq!(result["solved"], 5);
+    assert!(result["average_guesses"].as_f64().unwrap() >= 1.0);
+
+    let output = bullsncows(&["bench", "--length", "3", "--solver", "entropy,most-parts,worst-case", "--games", "5", "--seed", "1", "--format", "json"], "");
+    assert!(output.status.success());
+    let results: Vec<serde_json::Value> = stdout_of(&output).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[2]["solver"], "worst-case");
+    assert!(results.iter().all(|result| result["solved"] == 5));
+
+    let output = bullsncows(&["bench", "--length", "3", "--solver", "baseline,expected-size", "--games", "2"], "");
+    assert!(stdout_of(&output).contains("solver          average  worst"));
+}
+
+#[test]
+fn test_optimal() {
+    let output = bullsncows(&["optimal", "--length", "2", "--alphabet", "0123"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("average guesses: 2.5000 (30 guesses for 12 secrets)\n01 (12 secrets)\n"));
+    assert!(stdout.contains("  0b 0c -> 23 (2 secrets)"));
+
+    let output = bullsncows(&["optimal", "--length", "2", "--alphabet", "0123", "--format", "json"], "");
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    assert_eq!(result["total_guesses"], 30);
+    assert_eq!(result["tree"]["branches"]["0b 0c"]["guess"], "23");
+
+    // five digits are out of reach, as a command and as a solver
+    for args in [&["optimal", "--length", "5"][..], &["solve", "--solver", "optimal", "--length", "5", "--secret", "12345"][..], &["bench", "--solver", "optimal", "--length", "5", "--games", "1"][..]] {
+        let output = bullsncows(args, "");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("30240 possible secrets"), "{:?}", args);
+    }
+    // the feedback table holds a byte per feedback, 3^5 marks do not fit
+    let output = bullsncows(&["optimal", "--length", "5", "--alphabet", "012345", "--feedback", "positional"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("positional feedback is limited to 4 symbols here"));
+}
+
+#[test]
+fn test_static() {
+    let output = bullsncows(&["static", "--length", "2", "--alphabet", "0123"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout_of(&output), "2 guesses tell all 12 secrets apart, no fewer can\n01\n02\n");
+
+    let output = bullsncows(&["static", "--length", "3", "--alphabet", "01234", "--feedback", "bulls", "--format", "json"], "");
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    assert_eq!(result["secrets"], 60);
+    assert_eq!(result["guesses"].as_array().unwrap().len(), 6);
+    assert_eq!(result["smallest"], true);
+
+    let output = bullsncows(&["static", "--length", "5"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let output = bullsncows(&["static", "--length", "5", "--alphabet", "012345", "--feedback", "positional"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("positional feedback is limited to 4 symbols here"));
+}
+
+#[test]
+fn test_replay() {
+    let path = std::env::temp_dir().join(format!("bullsncows_replay_{}.txt", std::process::id()));
+    std::fs::write(&path, "Number to guess: 8536\n\n9435 1b 1c\n3865 0b 4c\n6538 2b 1c\n").unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = bullsncows(&["replay", file], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout_of(&output).contains("line 5: 6538 recorded 2b 1c, actual 2b 2c"));
+
+    let output = bullsncows(&["replay", file, "--secret", "5836", "--format", "json"], "");
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    assert_eq!(result["secret"], "5836");
+
+    // the secrets listed are the ones of the variant, here Mastermind codes with repeated colors
+    let output = bullsncows(&["solve", "--colors", "6", "--secret", "RRGB"], "");
+    let transcript: String = stdout_of(&output).lines().filter(|line| !line.starts_with("Number to guess")).map(|line| format!("{}\n", line)).collect();
+    std::fs::write(&path, transcript).unwrap();
+    let output = bullsncows(&["replay", file, "--colors", "6"], "");
+    assert!(output.status.success());
+    assert!(stdout_of(&output).ends_with("RRGB\n1 consistent secrets\n"));
+    let output = bullsncows(&["replay", file, "--length", "3"], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    // what solve prints for every kind of feedback replays with the same kind
+    for kind in ["total", "bulls", "any-bull", "positional"] {
+        let output = bullsncows(&["solve", "--length", "3", "--secret", "789", "--feedback", kind], "");
+        std::fs::write(&path, stdout_of(&output)).unwrap();
+        let output = bullsncows(&["replay", file, "--length", "3", "--feedback", kind], "");
+        assert!(output.status.success(), "{} {}", kind, String::from_utf8_lossy(&output.stderr));
+        assert!(stdout_of(&output).ends_with("lines consistent with secret 789\n"));
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_pico_fermi_bagels() {
+    let output = bullsncows(&["solve", "--length", "3", "--secret", "789", "--pico-fermi-bagels"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.trim_end().ends_with("789 Fermi Fermi Fermi"));
+
+    // the transcript replays like a bulls and cows one
+    let path = std::env::temp_dir().join(format!("bullsncows_pfb_{}.txt", std::process::id()));
+    std::fs::write(&path, &stdout).unwrap();
+    let output = bullsncows(&["replay", path.to_str().unwrap()], "");
+    assert!(output.status.success());
+    std::fs::remove_file(&path).unwrap();
+
+    let output = bullsncows(&["assist", "--length", "3", "--pico-fermi-bagels"], "Bagels\nFermi Fermi Fermi\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Clues? "));
+    assert!(stdout.contains("(210 possible numbers left)"));
+    assert!(stdout.contains("Solved: "));
+
+    // two clues look like "1b 2c" but are clues all the same
+    let output = bullsncows(&["assist", "--length", "3", "--pico-fermi-bagels"], "Fermi Pico\nFermi Fermi\n");
+    let stdout = stdout_of(&output);
+    assert!(!stdout.contains("Invalid input!"), "{}", stdout);
+    assert_eq!(stdout.matches("Clues? ").count(), 3);
+
+    let output = bullsncows(&["play", "--length", "3", "--seed", "5", "--pico-fermi-bagels"], "345\nquit\n");
+    let line = stdout_of(&output).lines().nth(1).unwrap().to_string();
+    assert!(line == "Bagels" || line.split(' ').all(|word| word == "Fermi" || word == "Pico"), "{}", line);
+}
+
+#[test]
+fn test_reduced_feedback() {
+    let output = bullsncows(&["solve", "--length", "3", "--secret", "789", "--feedback", "total"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.trim_end().ends_with("789 3b 0c"));
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[2..lines.len() - 1].iter().all(|line| line.ends_with(" found")), "{}", stdout);
+
+    let output = bullsncows(&["assist", "--length", "3", "--feedback", "any-bull"], "no\nwin\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Any bull? "));
+    assert!(stdout.contains("Solved: "));
+
+    let output = bullsncows(&["engine", "--length", "3", "--feedback", "bulls"], "guess\nfeedback 1\nfeedback 1 2\n");
+    let lines: Vec<String> = stdout_of(&output).lines().map(String::from).collect();
+    assert!(lines[1].starts_with("ok "));
+    // cows are not told with bulls only
+    assert_eq!(lines[2], "error invalid feedback");
+
+    let output = bullsncows(&["solve", "--feedback", "total", "--pico-fermi-bagels"], "");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_positional_feedback() {
+    let output = bullsncows(&["solve", "--secret", "1234", "--feedback", "positional", "--seed", "1"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.trim_end().ends_with("1234 4b 0c"));
+    let lines: Vec<&str> = stdout.lines().collect();
+    // the marks of every symbol, e.g. "0123 -CCC"
+    assert!(lines[2..lines.len() - 1].iter().all(|line| line.split_once(' ').unwrap().1.chars().all(|c| "BC-".contains(c))), "{}", stdout);
+
+    let output = bullsncows(&["solve", "--secret", "1234", "--feedback", "positional", "--format", "json"], "");
+    let result: serde_json::Value = serde_json::from_str(&stdout_of(&output)).unwrap();
+    let last = result["guesses"].as_array().unwrap().last().unwrap().clone();
+    assert_eq!(last["marks"], "BBBB");
+    assert_eq!(last["cows"], 0);
+
+    let output = bullsncows(&["engine", "--length", "3", "--feedback", "positional"], "guess\nfeedback bc-\nfeedback 1 1\n");
+    let lines: Vec<String> = stdout_of(&output).lines().map(String::from).collect();
+    assert!(lines[1].starts_with("ok "));
+    assert_eq!(lines[2], "error invalid feedback");
+}
+
+#[test]
+fn test_words() {
+    let path = std::env::temp_dir().join(format!("bullsncows_words_{}.txt", std::process::id()));
+    std::fs::write(&path, "word\nlamp\nbird\nfish\nhello\nbook\nCake\nmint\nlion\n").unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = bullsncows(&["solve", "--words", file, "--length", "4", "--secret", "lion"], "");
+    assert!(output.status.success());
+    assert!(stdout_of(&output).trim_end().ends_with("lion 4b 0c"));
+    // words with a repeated letter are left out
+    let output = bullsncows(&["solve", "--words", file, "--length", "4", "--secret", "book"], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = bullsncows(&["play", "--words", file, "--length", "4"], "abcd\ncake\nquit\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("one of 7 words"));
+    assert!(stdout.contains("Invalid input!"));
+
+    // no word of the file has 7 letters
+    let output = bullsncows(&["play", "--words", file, "--length", "7"], "");
+    assert_eq!(output.status.code(), Some(2));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_multi() {
+    let output = bullsncows(&["multi", "--length", "3", "--boards", "3", "--bot", "--seed", "4"], "");
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines.last().unwrap().starts_with("Solved all 3 secrets after "));
+    // every board is won exactly once
+    for board in 0..3 {
+        let wins = lines[1..lines.len() - 1].iter().filter(|line| line.split(" | ").nth(board + 1) == Some("3b 0c")).count();
+        assert_eq!(wins, 1, "{}", stdout);
+    }
+
+    let output = bullsncows(&["multi", "--length", "3", "--boards", "2", "--seed", "4"], "0123\n012\nquit\n");
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Invalid input!"));
+    assert!(stdout.contains("\n012 | "));
+    assert!(stdout.contains("Solved 0 of 2 secrets, they were "));
+}
+
+#[test]
+fn test_serve() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bullsncows"))
+        .args(["serve", "--port", "0", "--length", "3", "--debug"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("cannot start bullsncows");
+    let mut server_output = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    server_output.read_line(&mut line).unwrap();
+    let address = line.trim().strip_prefix("Listening on ").unwrap().to_string();
+
+    let stream = TcpStream::connect(&address).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    let mut answer = String::new();
+    while answer.is_empty() {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        if let Some(debug_answer) = line.trim().strip_prefix("[debug] answer: ") {
+            answer = debug_answer.to_string();
+        }
+    }
+    writeln!(writer, "{}", answer).unwrap();
+    line.clear();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line.trim(), "Bulls: 3, cows: 0");
+    line.clear();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line.trim(), "You won after 1 guesses");
+
+    // a line without end closes the connection instead of being buffered
+    let stream = TcpStream::connect(&address).unwrap();
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(10))).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    let _ = writer.write_all(&vec![b'1'; 100_000]);
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => continue,
+            Err(e) => {
+                assert!(e.kind() != std::io::ErrorKind::WouldBlock && e.kind() != std::io::ErrorKind::TimedOut, "{}", e);
+                break;
+            }
+        }
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn test_debug_environment() {
+    // read as a flag by `play` and by the default command alike
+    for (args, value, shown) in [(&["play", "--length", "3"][..], "1", true), (&["play", "--length", "3"][..], "false", false), (&[][..], "true", true), (&[][..], "false", false)] {
+        let output = Command::new(env!("CARGO_BIN_EXE_bullsncows"))
+            .args(args)
+            .env("BULLSNCOWS_DEBUG", value)
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+        assert_eq!(stdout_of(&output).contains("[debug] answer: "), shown, "{:?} {}", args, value);
+    }
+}
+
+#[test]
+fn test_scores() {
+    let path = std::env::temp_dir().join(format!("bullsncows_scores_{}.tsv", std::process::id()));
+    let store = path.to_str().unwrap();
+    let output = bullsncows(&["play", "--length", "3", "--seed", "9", "--debug"], "");
+    let stdout = stdout_of(&output);
+    let answer = stdout.lines().find_map(|line| line.strip_prefix("[debug] answer: ")).unwrap().to_string();
+    for player in ["ann", "bob"] {
+        let input = format!("{}\nn\n", answer);
+        bullsncows(&["play", "--length", "3", "--seed", "9", "--player", player, "--store", store], &input);
+    }
+    bullsncows(&["play", "--length", "3", "--seed", "9", "--player", "cid", "--store", store], "quit\nn\n");
+
+    let stdout = stdout_of(&bullsncows(&["scores", "best", "--player", "ann", "--store", store], ""));
+    assert!(stdout.starts_with("3/0123456789"));
+    assert!(stdout.contains("1 guesses"));
+    let stdout = stdout_of(&bullsncows(&["scores", "leaderboard", "--length", "3", "--store", store], ""));
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(!stdout.contains("cid"));
+    let stdout = stdout_of(&bullsncows(&["scores", "histogram", "--store", store], ""));
+    assert!(stdout.starts_with("  1 guesses     2 "));
+    std::fs::remove_file(&path).unwrap();
+}
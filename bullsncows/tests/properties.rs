@@ -0,0 +1,127 @@
+#![allow(clippy::needless_return)]
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use bullsncows::algos::create_solver;
+use bullsncows::algos::algos_utils::{filter_numbers, get_bulls_and_cows};
+use bullsncows::algos::feedback::FEEDBACK_KINDS;
+use bullsncows::game::Game;
+use bullsncows::ruleset::Ruleset;
+
+// Invariants of the scoring and of the filtering of candidates, over random numbers and variants.
+// Symbols include multi-byte ones so that both scoring paths are covered.
+
+const SYMBOLS: [char; 8] = ['0', '1', '2', 'a', 'b', 'é', 'ж', '٣'];
+
+fn number(length: usize) -> impl Strategy<Value = Vec<char>> {
+    return proptest::collection::vec(proptest::sample::select(&SYMBOLS[..]), length);
+}
+
+// two numbers of the same length, repeated symbols allowed
+fn number_pair() -> impl Strategy<Value = (Vec<char>, Vec<char>)> {
+    return (1..8usize).prop_flat_map(|length| (number(length), number(length)));
+}
+
+fn to_string(symbols: &[char]) -> String {
+    return symbols.iter().collect();
+}
+
+// a valid variant over the symbols, small enough to list all its secrets
+fn ruleset(symbols: &'static [char]) -> impl Strategy<Value = Ruleset> {
+    return (1..=4usize, 0..=3usize, any::<bool>(), 0..FEEDBACK_KINDS.len()).prop_map(move |(length, extra_symbols, repeats, kind)| {
+        let alphabet: Vec<char> = symbols[..(length + extra_symbols).min(symbols.len())].to_vec();
+        return Ruleset { feedback_kind: FEEDBACK_KINDS[kind], ..Ruleset::new(length, &alphabet, repeats) };
+    });
+}
+
+proptest! {
+    #[test]
+    fn test_score_is_bounded((guess, secret) in number_pair()) {
+        let (bulls, cows) = get_bulls_and_cows(to_string(&guess), to_string(&secret)).unwrap();
+        prop_assert!(bulls + cows <= guess.len());
+        prop_assert_eq!(bulls, guess.iter().zip(secret.iter()).filter(|(a, b)| a == b).count());
+    }
+
+    #[test]
+    fn test_score_against_itself(number in (1..8usize).prop_flat_map(number)) {
+        prop_assert_eq!(get_bulls_and_cows(to_string(&number), to_string(&number)), Some((number.len(), 0)));
+    }
+
+    #[test]
+    fn test_score_is_symmetric((guess, secret) in number_pair()) {
+        prop_assert_eq!(get_bulls_and_cows(to_string(&guess), to_string(&secret)), get_bulls_and_cows(to_string(&secret), to_string(&guess)));
+    }
+
+    #[test]
+    fn test_score_of_different_lengths(guess in number(3), secret in number(4)) {
+        prop_assert_eq!(get_bulls_and_cows(to_string(&guess), to_string(&secret)), None);
+    }
+
+    // permuting the positions and relabeling the symbols of both numbers the same way keeps the score
+    #[test]
+    fn test_score_is_invariant_under_symmetries(
+        (guess, secret, positions) in number_pair().prop_flat_map(|(guess, secret)| {
+            let positions = Just((0..guess.len()).collect::<Vec<usize>>()).prop_shuffle();
+            return (Just(guess), Just(secret), positions);
+        }),
+        labels in Just(SYMBOLS.to_vec()).prop_shuffle(),
+    ) {
+        let transform = |number: &[char]| -> String {
+            return positions.iter().map(|&position| labels[SYMBOLS.iter().position(|&symbol| symbol == number[position]).unwrap()]).collect();
+        };
+        prop_assert_eq!(get_bulls_and_cows(transform(&guess), transform(&secret)), get_bulls_and_cows(to_string(&guess), to_string(&secret)));
+    }
+
+    // truthful feedback never rules the secret out, and every number kept could have given it
+    #[test]
+    fn test_filtering_keeps_the_secret(rules in ruleset(&SYMBOLS), secret in any::<Index>(), guesses in proptest::collection::vec(any::<Index>(), 0..5)) {
+        let numbers = rules.generate_numbers();
+        let secret = secret.get(&numbers);
+        let past_guesses: Vec<(String, (usize, usize))> = guesses.iter().map(|guess| {
+            let guess = guess.get(&numbers);
+            return (guess.clone(), rules.feedback_kind.get_feedback(guess, secret).unwrap());
+        }).collect();
+        let remaining = filter_numbers(&numbers, &past_guesses, rules.feedback_kind);
+        prop_assert!(remaining.contains(secret));
+        for number in remaining.iter() {
+            prop_assert!(past_guesses.iter().all(|(guess, feedback)| rules.feedback_kind.get_feedback(guess, number) == Some(*feedback)));
+        }
+    }
+
+    // the same through a game: the secret stays among the numbers the player cannot rule out
+    #[test]
+    fn test_game_keeps_the_secret(rules in ruleset(&SYMBOLS), secret in any::<Index>(), guesses in proptest::collection::vec(any::<Index>(), 0..5)) {
+        let numbers = rules.generate_numbers();
+        let mut game = Game::new_with_ruleset_and_answer(&rules, secret.get::<String>(&numbers)).unwrap();
+        for guess in guesses.iter() {
+            if game.is_over() {
+                break;
+            }
+            prop_assert!(game.make_guess(guess.get::<String>(&numbers)).is_some());
+        }
+        prop_assert!(game.get_consistent_numbers().iter().any(|number| number == game.get_answer()));
+    }
+}
+
+proptest! {
+    // solvers play whole games, fewer cases
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    // the solvers keep the secret among their numbers until they guess it
+    #[test]
+    fn test_solvers_keep_the_secret(rules in ruleset(&SYMBOLS), secret in any::<Index>(), name in proptest::sample::select(vec!["baseline", "entropy", "entropy-2", "worst-case"])) {
+        let numbers = rules.generate_numbers();
+        let secret = secret.get(&numbers);
+        let mut solver = create_solver(name, &rules, Some(1), None).unwrap();
+        for _ in 0..numbers.len() {
+            let guess = solver.guess().unwrap();
+            let (bulls, cows) = rules.feedback_kind.get_feedback(&guess, secret).unwrap();
+            if bulls == rules.length {
+                return Ok(());
+            }
+            prop_assert_eq!(solver.incorporate_guess_feedback(bulls, cows), Some(true));
+            prop_assert!(solver.get_numbers().contains(secret));
+        }
+        prop_assert!(false, "{} did not find {}", name, secret);
+    }
+}